@@ -6,4 +6,5 @@ pub const QUOTE_MINT: &str = "So11111111111111111111111111111111111111112";
 pub static VAULT_PREFIX: &str = "vault";
 pub static TOKEN_VAULT_PREFIX: &str = "token_vault";
 pub static LP_MINT_PREFIX: &str = "lp_mint";
+pub static LOCK_ESCROW_PREFIX: &str = "lock_escrow";
 pub static RPC_URL: &str = "https://devnet.helius-rpc.com/?api-key=";
\ No newline at end of file
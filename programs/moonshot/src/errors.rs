@@ -86,4 +86,136 @@ pub enum ContractError {
 
     #[msg("Invalid Migration Authority")]
     InvalidMigrationAuthority,
+
+    #[msg("Instruction not allowed in the current curve status")]
+    InvalidCurveStatus,
+
+    #[msg("Invalid curve status transition")]
+    InvalidStatusTransition,
+
+    #[msg("Pool price deviates too far from the curve price")]
+    PriceDeviationExceeded,
+
+    #[msg("Pool does not match the migrated curve")]
+    InvalidPool,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    InvalidPendingAuthority,
+
+    #[msg("Invalid council signers or threshold")]
+    InvalidCouncil,
+
+    #[msg("Signer is not a council member")]
+    NotCouncilSigner,
+
+    #[msg("Proposal already approved by this signer")]
+    AlreadyApproved,
+
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal predates a council membership change")]
+    StaleProposal,
+
+    #[msg("Council is not the global authority")]
+    CouncilNotAuthority,
+
+    #[msg("No parameter change is queued")]
+    NoPendingParams,
+
+    #[msg("Queued parameter change is still timelocked")]
+    TimelockNotElapsed,
+
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Launching new curves is disabled")]
+    LaunchDisabled,
+
+    #[msg("Migration is disabled")]
+    MigrationDisabled,
+
+    #[msg("Withdrawals are disabled")]
+    WithdrawDisabled,
+
+    #[msg("Curve is paused")]
+    CurvePaused,
+
+    #[msg("Curve is delisted")]
+    CurveDelisted,
+
+    #[msg("Mint decimals must be positive")]
+    InvalidMintDecimals,
+
+    #[msg("Initial real token reserves must be positive")]
+    ZeroRealTokenReserves,
+
+    #[msg("Initial real token reserves exceed the token total supply")]
+    RealReservesExceedSupply,
+
+    #[msg("Initial virtual token reserves must exceed the real token reserves")]
+    VirtualReservesBelowReal,
+
+    #[msg("Initial virtual SOL reserves must be positive")]
+    ZeroVirtualSolReserves,
+
+    #[msg("Fee receiver is not set")]
+    FeeReceiverNotSet,
+
+    #[msg("Keeper reward exceeds the migrate fee")]
+    KeeperRewardExceedsFee,
+
+    #[msg("Basis points above 10000")]
+    InvalidBps,
+
+    #[msg("Invalid liquidity policy")]
+    InvalidLiquidityPolicy,
+
+    #[msg("Params delay must not be negative")]
+    NegativeParamsDelay,
+
+    #[msg("Account was written by a newer program version")]
+    UnsupportedAccountVersion,
+
+    #[msg("Fee decay ends before it starts")]
+    InvalidFeeSchedule,
+
+    #[msg("Launch config is disabled")]
+    LaunchConfigDisabled,
+
+    #[msg("Launch config does not match the curve")]
+    InvalidLaunchConfig,
+
+    #[msg("Whitelist entry has expired")]
+    WhitelistExpired,
+
+    #[msg("Whitelist launch quota used up")]
+    LaunchQuotaExceeded,
+
+    #[msg("Whitelist entry is bound to another launch config")]
+    WhitelistTierMismatch,
+
+    #[msg("Wallet is on the deny list")]
+    WalletDenied,
+
+    #[msg("Deny list entry account is required")]
+    DenyEntryMissing,
+
+    #[msg("No LP tokens to lock")]
+    ZeroLpAmount,
+
+    #[msg("Refunding curve only takes sells")]
+    RefundSellOnly,
+
+    #[msg("Holders still have tokens to refund")]
+    RefundsOutstanding,
 }
@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
 
-use crate::ProgramStatus;
+use crate::state::bonding_curve::CurveStatus;
+use crate::state::council::AdminAction;
+use crate::state::launch_config::{CurveConfig, FeeSchedule, MigrationConfig};
+use crate::{GlobalSettingsInput, LiquidityPolicy, ProgramStatus, Role};
+
+/// Layout version carried as the first field of every event. Bumped whenever any
+/// event gains, loses or reorders a field.
+pub const EVENT_SCHEMA_VERSION: u8 = 2;
 
 #[event]
 pub struct GlobalUpdateEvent {
+    pub schema_version: u8,
     pub global_authority: Pubkey,
     pub migration_authority: Pubkey,
     pub status: ProgramStatus,
@@ -16,6 +24,7 @@ pub struct GlobalUpdateEvent {
 
 #[event]
 pub struct CreateEvent {
+    pub schema_version: u8,
     pub mint: Pubkey,
     pub creator: Pubkey,
     pub name: String,
@@ -27,22 +36,37 @@ pub struct CreateEvent {
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
     pub token_total_supply: u64,
+    pub launch_config: Pubkey, // default for the Global settings
 }
 
 #[event]
 pub struct WithdrawEvent {
+    pub schema_version: u8,
     pub withdraw_authority: Pubkey,
-    pub mint: Pubkey,
-    pub fee_vault: Pubkey,
+    pub receiver: Pubkey,
 
     pub withdrawn: u64,
-    pub total_withdrawn: u64,
+    pub remaining: u64, // lamports left above rent
 
     pub withdraw_time: i64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TradeVenue {
+    BondingCurve,
+    Pool, // routed to the migrated pool
+}
+
+/// Part of a trade's fee paid to one recipient.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FeeShare {
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
 #[event]
 pub struct TradeEvent {
+    pub schema_version: u8,
     pub mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
@@ -54,10 +78,21 @@ pub struct TradeEvent {
     pub virtual_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
+    pub venue: TradeVenue,
+    pub trade_seq: u64, // per curve, increases by one with every swap
+    pub slot: u64,
+    // see `price_from_reserves`. Trades routed to the pool report the pool reserves and
+    // the pool fee instead of the curve's
+    pub price_before: u128,
+    pub price_after: u128,
+    pub market_cap_lamports: u64,
+    pub progress_bps: u64,   // toward graduation
+    pub fees: Vec<FeeShare>, // sums to `fee_lamports`
 }
 
 #[event]
 pub struct CompleteEvent {
+    pub schema_version: u8,
     pub user: Pubkey,
     pub mint: Pubkey,
     pub virtual_sol_reserves: u64,
@@ -65,8 +100,686 @@ pub struct CompleteEvent {
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
     pub timestamp: i64,
+    pub trade_seq: u64, // of the trade that completed the curve
+    pub slot: u64,
+}
+
+#[event]
+pub struct CurveStatusEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub from: CurveStatus,
+    pub to: CurveStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub lock_escrow: Pubkey, // default when nothing was locked
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SweepEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimLpFeesEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub lock_escrow: Pubkey,
+    pub protocol_token_a_amount: u64,
+    pub protocol_token_b_amount: u64,
+    pub creator_token_a_amount: u64,
+    pub creator_token_b_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolCreatedEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub excess_token_b: u64, // burned or moved to the treasury
+    pub migrate_fee: u64,
+    pub keeper_reward: u64, // part of `migrate_fee` paid to the migrator
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityPolicyEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub policy: LiquidityPolicy,
+    pub lp_amount: u64,
+    pub lock_amount: u64,
+    pub burn_amount: u64,
+    pub lock_escrow: Pubkey, // default when nothing was locked
+    pub timestamp: i64,
 }
 
 pub trait IntoEvent<T: anchor_lang::Event> {
+    #[allow(clippy::wrong_self_convention)]
     fn into_event(&self) -> T;
 }
+
+#[event]
+pub struct AuthorityProposedEvent {
+    pub schema_version: u8,
+    pub global_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityAcceptedEvent {
+    pub schema_version: u8,
+    pub previous_authority: Pubkey,
+    pub global_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityCancelledEvent {
+    pub schema_version: u8,
+    pub global_authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilUpdateEvent {
+    pub schema_version: u8,
+    pub council: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub generation: u64,
+}
+
+#[event]
+pub struct ProposalCreatedEvent {
+    pub schema_version: u8,
+    pub council: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApprovedEvent {
+    pub schema_version: u8,
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecutedEvent {
+    pub schema_version: u8,
+    pub proposal: Pubkey,
+    pub executor: Pubkey,
+    pub action: AdminAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamsQueuedEvent {
+    pub schema_version: u8,
+    pub params: GlobalSettingsInput,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamsExecutedEvent {
+    pub schema_version: u8,
+    pub params: GlobalSettingsInput,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamsCancelledEvent {
+    pub schema_version: u8,
+    pub params: GlobalSettingsInput,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGrantedEvent {
+    pub schema_version: u8,
+    pub role: Role,
+    pub holder: Pubkey,
+    pub previous_holder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevokedEvent {
+    pub schema_version: u8,
+    pub role: Role,
+    pub holder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveFlagsEvent {
+    pub schema_version: u8,
+    pub mint: Pubkey,
+    pub paused: bool,
+    pub delisted: bool,
+    pub reason: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchConfigEvent {
+    pub schema_version: u8,
+    pub launch_config: Pubkey,
+    pub index: u16,
+    pub name: String,
+    pub enabled: bool,
+    pub curve: CurveConfig,
+    pub fees: FeeSchedule,
+    pub migration: MigrationConfig,
+}
+
+#[event]
+pub struct WhitelistAddedEvent {
+    pub schema_version: u8,
+    pub creator: Pubkey,
+    pub whitelist: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WhitelistRemovedEvent {
+    pub schema_version: u8,
+    pub creator: Pubkey,
+    pub whitelist: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WhitelistUpdatedEvent {
+    pub schema_version: u8,
+    pub creator: Pubkey,
+    pub whitelist: Pubkey,
+    pub max_launches: Option<u32>,
+    pub launches_used: u32,
+    pub expires_at: Option<i64>,
+    pub launch_config: Option<u16>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WhitelistRootEvent {
+    pub schema_version: u8,
+    pub root: [u8; 32], // zero disables the merkle whitelist
+    pub previous_root: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletDeniedEvent {
+    pub schema_version: u8,
+    pub wallet: Pubkey,
+    pub reason: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletAllowedEvent {
+    pub schema_version: u8,
+    pub wallet: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DenyListToggledEvent {
+    pub schema_version: u8,
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigratedEvent {
+    pub schema_version: u8,
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{QUOTE_MINT, VAULT_SEED};
+    use crate::instructions::{LpLocker, PoolCreator};
+    use crate::state::bonding_curve::{BondingCurve, CreateBondingCurveParams};
+    use crate::state::meteora::get_lock_escrow_address;
+    use crate::state::testing::{
+        mint_data, program_logs, token_account_data, TestAccount, TEST_CLOCK,
+    };
+    use crate::state::whitelist::{Whitelist, WhitelistInput};
+    use crate::Global;
+    use anchor_lang::__private::base64::{engine::general_purpose::STANDARD, Engine};
+    use anchor_lang::event::EVENT_IX_TAG_LE;
+    use anchor_lang::Event;
+    use anchor_spl::token::{self, Mint, Token, TokenAccount};
+    use std::str::FromStr;
+
+    /// Decodes `T` from an event payload, `None` for other events.
+    fn decode<T: Event>(data: &[u8]) -> Option<T> {
+        let data = data.strip_prefix(T::DISCRIMINATOR)?;
+        T::deserialize(&mut &data[..]).ok()
+    }
+
+    /// Events of type `T` logged with `emit!`.
+    fn parse_logs<T: Event>(logs: &[String]) -> Vec<T> {
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter_map(|data| decode(&data))
+            .collect()
+    }
+
+    /// Events of type `T` in the data of the program's inner instructions, as
+    /// emitted with `emit_cpi!`.
+    fn parse_inner_instructions<T: Event>(inner: &[Vec<u8>]) -> Vec<T> {
+        inner
+            .iter()
+            .filter_map(|data| data.strip_prefix(EVENT_IX_TAG_LE))
+            .filter_map(|data| decode(data))
+            .collect()
+    }
+
+    /// What `emit_cpi!` passes to the self CPI.
+    fn cpi_data(event: &impl Event) -> Vec<u8> {
+        [EVENT_IX_TAG_LE, &event.data()].concat()
+    }
+
+    /// A curve bought out to completion, held by the program with its reserves.
+    fn complete_curve(global: &Global, mint: Pubkey) -> TestAccount {
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: Some(0),
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let mut curve = BondingCurve::default();
+        curve.update_from_params(
+            mint,
+            Pubkey::new_unique(),
+            &global.launch_terms(),
+            &params,
+            &TEST_CLOCK,
+            0,
+        );
+        curve.transition(CurveStatus::Trading, 0).unwrap();
+        assert!(curve.apply_buy(u64::MAX / 2).unwrap().completes);
+        curve.transition(CurveStatus::Complete, 0).unwrap();
+        TestAccount {
+            lamports: Rent::default().minimum_balance(8 + BondingCurve::INIT_SPACE)
+                + curve.real_sol_reserves,
+            ..TestAccount::program_owned(&curve)
+        }
+    }
+
+    #[test]
+    fn test_parse_migration_events() {
+        let global = Global {
+            liquidity_policy: LiquidityPolicy::Split { lock_bps: 7_500 },
+            ..Default::default()
+        };
+        let mut token_b_mint = TestAccount::new(token::ID, mint_data(global.token_total_supply));
+        let mint = token_b_mint.key;
+        let mut curve = complete_curve(&global, mint);
+        let mut curve_tokens = TestAccount::new(
+            token::ID,
+            token_account_data(
+                mint,
+                curve.key,
+                global.token_total_supply - global.initial_real_token_reserves,
+            ),
+        );
+        let mut token_a_mint = TestAccount {
+            key: Pubkey::from_str(QUOTE_MINT).unwrap(),
+            ..TestAccount::new(token::ID, mint_data(0))
+        };
+        let mut config = TestAccount {
+            key: global.migration_config().meteora_config,
+            ..TestAccount::new(Pubkey::default(), vec![])
+        };
+        let mut token_program = TestAccount::program(token::ID);
+        let mut global = TestAccount::program_owned(&global);
+        let mut pool = TestAccount::new(Pubkey::default(), vec![]);
+        let mut lp_mint = TestAccount::new(token::ID, mint_data(1_000));
+        let mut payer_pool_lp = TestAccount::new(
+            token::ID,
+            token_account_data(lp_mint.key, Pubkey::new_unique(), 1_000),
+        );
+        let (vault, vault_bump) = Pubkey::find_program_address(&[VAULT_SEED], &crate::ID);
+        let mut lock_escrow = TestAccount {
+            key: get_lock_escrow_address(&pool.key, &vault),
+            ..TestAccount::new(Pubkey::default(), vec![])
+        };
+        let mut unchecked = TestAccount::new(Pubkey::default(), vec![]);
+
+        let global_info = global.info();
+        let curve_info = curve.info();
+        let mint_info = token_b_mint.info();
+        let curve_tokens_info = curve_tokens.info();
+        let token_program_info = token_program.info();
+        let global = Account::<Global>::try_from(&global_info).unwrap();
+        let mut bonding_curve = Account::<BondingCurve>::try_from(&curve_info).unwrap();
+        let token_b_mint = Account::<Mint>::try_from(&mint_info).unwrap();
+        let curve_tokens = Account::<TokenAccount>::try_from(&curve_tokens_info).unwrap();
+        let token_program = Program::<Token>::try_from(&token_program_info).unwrap();
+        let pool = pool.info();
+        let lp_mint = lp_mint.info();
+        let payer_pool_lp = payer_pool_lp.info();
+        let lock_escrow = lock_escrow.info();
+        let unchecked = unchecked.info();
+
+        let (events, logs) = program_logs(|| {
+            let (migrated, created) = PoolCreator {
+                global: &global,
+                bonding_curve: &mut bonding_curve,
+                bonding_curve_bump: 0,
+                launch_config: None,
+                token_b_mint: &token_b_mint,
+                bonding_curve_token_account: &curve_tokens,
+                treasury_token_account: None,
+                token_program: &token_program,
+                migration_vault: unchecked.clone(),
+                pool: pool.clone(),
+                config: config.info(),
+                lp_mint: lp_mint.clone(),
+                a_vault_lp: unchecked.clone(),
+                b_vault_lp: unchecked.clone(),
+                token_a_mint: token_a_mint.info(),
+                a_vault: unchecked.clone(),
+                b_vault: unchecked.clone(),
+                a_token_vault: unchecked.clone(),
+                b_token_vault: unchecked.clone(),
+                a_vault_lp_mint: unchecked.clone(),
+                b_vault_lp_mint: unchecked.clone(),
+                payer_token_a: unchecked.clone(),
+                payer_token_b: unchecked.clone(),
+                payer_pool_lp: payer_pool_lp.clone(),
+                protocol_token_a_fee: unchecked.clone(),
+                protocol_token_b_fee: unchecked.clone(),
+                payer: unchecked.clone(),
+                mint_metadata: unchecked.clone(),
+                rent: unchecked.clone(),
+                metadata_program: unchecked.clone(),
+                vault_program: unchecked.clone(),
+                associated_token_program: unchecked.clone(),
+                system_program: unchecked.clone(),
+            }
+            .create_pool(&[])
+            .unwrap();
+            emit!(migrated);
+            emit!(created);
+
+            let (locked, policy) = LpLocker {
+                global: &global,
+                bonding_curve: &mut bonding_curve,
+                vault: unchecked.clone(),
+                vault_bump,
+                pool: pool.clone(),
+                lp_mint: lp_mint.clone(),
+                a_vault_lp: unchecked.clone(),
+                b_vault_lp: unchecked.clone(),
+                token_b_mint: mint_info.clone(),
+                a_vault: unchecked.clone(),
+                b_vault: unchecked.clone(),
+                a_vault_lp_mint: unchecked.clone(),
+                b_vault_lp_mint: unchecked.clone(),
+                payer_pool_lp: payer_pool_lp.clone(),
+                vault_pool_lp: unchecked.clone(),
+                payer: unchecked.clone(),
+                token_program: token_program_info.clone(),
+                associated_token_program: unchecked.clone(),
+                system_program: unchecked.clone(),
+                lock_escrow: lock_escrow.clone(),
+                escrow_vault: unchecked.clone(),
+            }
+            .lock_pool()
+            .unwrap();
+            emit!(locked);
+            emit!(policy);
+            // what the handlers pass to `emit_cpi!`
+            vec![
+                cpi_data(&migrated),
+                cpi_data(&created),
+                cpi_data(&locked),
+                cpi_data(&policy),
+            ]
+        });
+        assert_eq!(logs.len(), 4);
+        assert_eq!(bonding_curve.status, CurveStatus::LpLocked);
+
+        // a migrate transaction also carries unrelated self CPIs
+        let inner: Vec<Vec<u8>> = [events, vec![vec![1, 2, 3]]].concat();
+        for pools in [
+            parse_logs::<PoolCreatedEvent>(&logs),
+            parse_inner_instructions(&inner),
+        ] {
+            assert_eq!(pools.len(), 1);
+            assert_eq!(pools[0].mint, mint);
+            assert_eq!(pools[0].pool, *pool.key);
+            assert_eq!(pools[0].lp_mint, *lp_mint.key);
+            assert_eq!(pools[0].migrate_fee, global.migrate_fee_amount);
+            assert_eq!(pools[0].keeper_reward, global.keeper_reward_amount);
+            assert_eq!(pools[0].timestamp, TEST_CLOCK.unix_timestamp);
+            // the curve's tokens are paired in the pool or handed on as excess
+            assert_eq!(
+                pools[0].token_b_amount + pools[0].excess_token_b,
+                global.token_total_supply - global.initial_real_token_reserves
+            );
+        }
+
+        let statuses: Vec<CurveStatusEvent> = parse_logs(&logs);
+        let transitions: Vec<_> = statuses
+            .iter()
+            .map(|event| (event.from, event.to))
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (CurveStatus::Complete, CurveStatus::Migrated),
+                (CurveStatus::Migrated, CurveStatus::LpLocked),
+            ]
+        );
+        assert_eq!(
+            parse_inner_instructions::<CurveStatusEvent>(&inner).len(),
+            2
+        );
+
+        let policies: Vec<LiquidityPolicyEvent> = parse_logs(&logs);
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].mint, mint);
+        assert_eq!(policies[0].policy, global.liquidity_policy);
+        assert_eq!(policies[0].lp_amount, 1_000);
+        assert_eq!(policies[0].lock_amount, 750);
+        assert_eq!(policies[0].burn_amount, 250);
+        assert_eq!(policies[0].lock_escrow, *lock_escrow.key);
+    }
+
+    #[test]
+    fn test_parse_trade_events() {
+        let global = Global::default();
+        let mut curve = BondingCurve::default();
+        curve.update_from_params(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &global.launch_terms(),
+            &CreateBondingCurveParams {
+                name: "test".to_string(),
+                symbol: "test".to_string(),
+                uri: "test".to_string(),
+                start_time: Some(0),
+                launch_config: None,
+                whitelist_proof: None,
+            },
+            &TEST_CLOCK,
+            0,
+        );
+        curve.transition(CurveStatus::Trading, 0).unwrap();
+        let user = Pubkey::new_unique();
+        let fee_receiver = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        let (_, logs) = program_logs(|| {
+            for sol_amount in [1_000_000_000, 2_000_000_000] {
+                let price_before = curve.marginal_price().unwrap();
+                let bought = curve.apply_buy(sol_amount).unwrap();
+                curve.next_trade().unwrap();
+                let fees = vec![
+                    FeeShare {
+                        recipient: fee_receiver,
+                        lamports: sol_amount / 100,
+                    },
+                    FeeShare {
+                        recipient: creator,
+                        lamports: sol_amount / 200,
+                    },
+                ];
+                emit!(curve
+                    .trade_event(
+                        &global,
+                        user,
+                        true,
+                        [bought.sol_amount, bought.token_amount],
+                        price_before,
+                        fees,
+                    )
+                    .unwrap());
+            }
+        });
+
+        // the version byte directly follows the discriminator of every event
+        for log in &logs {
+            let data = STANDARD
+                .decode(log.strip_prefix("Program data: ").unwrap())
+                .unwrap();
+            assert_eq!(data[8], EVENT_SCHEMA_VERSION);
+        }
+
+        let trades: Vec<TradeEvent> = parse_logs(&logs);
+        let seqs: Vec<u64> = trades.iter().map(|trade| trade.trade_seq).collect();
+        assert_eq!(seqs, vec![1, 2]);
+        assert_eq!(curve.trade_count, 2);
+        for trade in &trades {
+            assert_eq!(trade.mint, curve.mint);
+            assert_eq!(trade.user, user);
+            assert_eq!(trade.venue, TradeVenue::BondingCurve);
+            assert_eq!(trade.slot, TEST_CLOCK.slot);
+            assert_eq!(trade.timestamp, TEST_CLOCK.unix_timestamp);
+            assert_eq!(trade.fees.len(), 2);
+            assert_eq!(
+                trade.fees.iter().map(|share| share.lamports).sum::<u64>(),
+                trade.fee_lamports
+            );
+            assert!(trade.price_after > trade.price_before);
+        }
+        assert_eq!(trades[0].fee_lamports, 15_000_000);
+        // each trade starts at the price the previous one left
+        assert_eq!(trades[1].price_before, trades[0].price_after);
+        assert_eq!(trades[1].real_sol_reserves, curve.real_sol_reserves);
+        assert_eq!(trades[1].progress_bps, curve.progress_bps(&global));
+    }
+
+    #[test]
+    fn test_parse_whitelist_events() {
+        let creator = Pubkey::new_unique();
+        let whitelist = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut entry = Whitelist::new(creator);
+        entry.update(WhitelistInput {
+            max_launches: Some(3),
+            expires_at: None,
+            launch_config: Some(2),
+        });
+        entry.use_launch(Some(2), 0).unwrap();
+
+        let (_, logs) = program_logs(|| {
+            emit!(entry.updated_event(whitelist, authority, Clock::get().unwrap().unix_timestamp))
+        });
+
+        let updated: Vec<WhitelistUpdatedEvent> = parse_logs(&logs);
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].creator, creator);
+        assert_eq!(updated[0].whitelist, whitelist);
+        assert_eq!(updated[0].max_launches, Some(3));
+        assert_eq!(updated[0].launches_used, 1);
+        assert_eq!(updated[0].expires_at, None);
+        assert_eq!(updated[0].launch_config, Some(2));
+        assert_eq!(updated[0].authority, authority);
+        assert_eq!(updated[0].timestamp, TEST_CLOCK.unix_timestamp);
+        assert!(parse_logs::<WhitelistAddedEvent>(&logs).is_empty());
+    }
+
+    #[test]
+    fn test_parse_program_data_logs() {
+        let global = Global {
+            global_authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let (_, emitted) = program_logs(|| {
+            emit!(global.into_event());
+            emit!(Whitelist::new(Pubkey::new_unique()).updated_event(
+                Pubkey::new_unique(),
+                global.global_authority,
+                0
+            ));
+        });
+        // events of transactions sent before emit_cpi! was used everywhere
+        let logs = [
+            vec!["Program log: Instruction: UpdateWl".to_string()],
+            emitted,
+            vec!["Program data: not base64!".to_string()],
+        ]
+        .concat();
+
+        let updates: Vec<GlobalUpdateEvent> = parse_logs(&logs);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].global_authority, global.global_authority);
+        assert_eq!(updates[0].status, global.status);
+        assert_eq!(updates[0].token_total_supply, global.token_total_supply);
+        assert_eq!(updates[0].mint_decimals, global.mint_decimals);
+
+        let updated: Vec<WhitelistUpdatedEvent> = parse_logs(&logs);
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].authority, global.global_authority);
+        assert!(parse_logs::<PoolCreatedEvent>(&logs).is_empty());
+    }
+}
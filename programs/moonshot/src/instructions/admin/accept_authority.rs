@@ -0,0 +1,45 @@
+use crate::{
+    errors::ContractError,
+    events::{AuthorityAcceptedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The proposed authority, signing proves the key is controlled.
+    #[account(mut)]
+    new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl AcceptAuthority<'_> {
+    pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.new_authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+        let previous_authority = global.global_authority;
+        global.accept_authority(ctx.accounts.new_authority.key)?;
+
+        emit_cpi!(AuthorityAcceptedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            previous_authority,
+            global_authority: global.global_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
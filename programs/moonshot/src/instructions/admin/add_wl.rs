@@ -1,16 +1,17 @@
-use anchor_lang::prelude::*;
 use crate::{
-    state::{global::*, whitelist::*},
     errors::ContractError,
+    events::{WhitelistAddedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, whitelist::*},
 };
+use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(new_creator: Pubkey)]
 pub struct AddWl<'info> {
     #[account(
-        mut,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     pub global: Box<Account<'info, Global>>,
@@ -18,15 +19,15 @@ pub struct AddWl<'info> {
     #[account(
         init,
         payer = admin,
-        space = 8 + 32,
+        space = 8 + Whitelist::INIT_SPACE,
         seeds = [Whitelist::SEED_PREFIX.as_bytes(), new_creator.key().as_ref()],
         bump
     )]
     pub whitelist: Account<'info, Whitelist>,
 
     #[account(
-        mut, 
-        constraint = admin.key() == global.global_authority.key() @ ContractError::InvalidGlobalAuthority
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
     )]
     pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -34,8 +35,19 @@ pub struct AddWl<'info> {
 
 impl AddWl<'_> {
     pub fn handler(ctx: Context<AddWl>, new_creator: Pubkey) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
         let whitelist = &mut ctx.accounts.whitelist;
-        whitelist.creator = new_creator.key();
+        whitelist.set_inner(Whitelist::new(new_creator));
+        emit_cpi!(WhitelistAddedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            creator: new_creator,
+            whitelist: ctx.accounts.whitelist.key(),
+            authority: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 }
@@ -0,0 +1,61 @@
+use crate::{
+    errors::ContractError,
+    events::{WhitelistAddedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, whitelist::*},
+};
+use anchor_lang::prelude::*;
+
+/// `add_wl` for many creators. Remaining accounts are the whitelist PDAs of
+/// `creators`, in the same order.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddWlBatch<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl AddWlBatch<'_> {
+    pub fn handler<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddWlBatch<'info>>,
+        creators: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
+        require_eq!(
+            creators.len(),
+            ctx.remaining_accounts.len(),
+            ContractError::InvalidArgument
+        );
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        for (creator, whitelist) in creators.into_iter().zip(ctx.remaining_accounts) {
+            Whitelist::create_entry(
+                whitelist,
+                &ctx.accounts.admin.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                creator,
+            )?;
+            emit_cpi!(WhitelistAddedEvent {
+                schema_version: EVENT_SCHEMA_VERSION,
+                creator,
+                whitelist: whitelist.key(),
+                authority: ctx.accounts.admin.key(),
+                timestamp,
+            });
+        }
+        Ok(())
+    }
+}
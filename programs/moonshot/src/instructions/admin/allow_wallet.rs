@@ -0,0 +1,49 @@
+use crate::{
+    errors::ContractError,
+    events::{WalletAllowedEvent, EVENT_SCHEMA_VERSION},
+    state::{deny_list::*, global::*},
+};
+use anchor_lang::prelude::*;
+
+/// Lifts a block by closing the wallet's deny entry, its rent goes back to the admin.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AllowWallet<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [DenyEntry::SEED_PREFIX.as_bytes(), deny_entry.wallet.as_ref()],
+        bump
+    )]
+    deny_entry: Account<'info, DenyEntry>,
+
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    admin: Signer<'info>,
+}
+
+impl AllowWallet<'_> {
+    pub fn handler(ctx: Context<AllowWallet>) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
+
+        emit_cpi!(WalletAllowedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            wallet: ctx.accounts.deny_entry.wallet,
+            authority: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{
+    events::{ProposalApprovedEvent, EVENT_SCHEMA_VERSION},
+    state::council::*,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    signer: Signer<'info>,
+
+    #[account(
+        seeds = [Council::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    council: Box<Account<'info, Council>>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::SEED_PREFIX.as_bytes(), proposal.index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    proposal: Box<Account<'info, Proposal>>,
+}
+
+impl ApproveProposal<'_> {
+    pub fn handler(ctx: Context<ApproveProposal>) -> Result<()> {
+        let council = &ctx.accounts.council;
+        let proposal = &mut ctx.accounts.proposal;
+        let approvals = council.approve(proposal, ctx.accounts.signer.key)?;
+
+        emit_cpi!(ProposalApprovedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            proposal: proposal.key(),
+            signer: ctx.accounts.signer.key(),
+            approvals,
+            threshold: council.threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
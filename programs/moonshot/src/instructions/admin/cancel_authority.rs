@@ -0,0 +1,46 @@
+use crate::{
+    errors::ContractError,
+    events::{AuthorityCancelledEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAuthority<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl CancelAuthority<'_> {
+    pub fn handler(ctx: Context<CancelAuthority>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+        let cancelled_authority = global.cancel_authority()?;
+
+        emit_cpi!(AuthorityCancelledEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            global_authority: global.global_authority,
+            cancelled_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
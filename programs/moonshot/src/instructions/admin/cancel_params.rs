@@ -0,0 +1,44 @@
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelParams<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl CancelParams<'_> {
+    pub fn handler(ctx: Context<CancelParams>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let params = ctx.accounts.global.cancel_settings()?;
+
+        emit_cpi!(ParamsCancelledEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            params,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    errors::ContractError,
+    events::{ProposalApprovedEvent, ProposalCreatedEvent, EVENT_SCHEMA_VERSION},
+    state::council::*,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Council::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    council: Box<Account<'info, Council>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [Proposal::SEED_PREFIX.as_bytes(), council.proposal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    proposal: Box<Account<'info, Proposal>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl CreateProposal<'_> {
+    pub fn handler(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        require!(action.is_valid(), ContractError::InvalidArgument);
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let council = &mut ctx.accounts.council;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.index = council.proposal_count;
+        proposal.generation = council.generation;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.created_at = timestamp;
+        // proposing counts as the first approval, also rejects non-members
+        let approvals = council.approve(proposal, &ctx.accounts.proposer.key())?;
+        council.proposal_count = council
+            .proposal_count
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticError)?;
+
+        emit_cpi!(ProposalCreatedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            council: council.key(),
+            proposal: proposal.key(),
+            index: proposal.index,
+            proposer: proposal.proposer,
+            action: proposal.action.clone(),
+            timestamp,
+        });
+        emit_cpi!(ProposalApprovedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            proposal: proposal.key(),
+            signer: proposal.proposer,
+            approvals,
+            threshold: council.threshold,
+            timestamp,
+        });
+        Ok(())
+    }
+}
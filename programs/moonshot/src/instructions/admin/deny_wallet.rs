@@ -0,0 +1,58 @@
+use crate::{
+    errors::ContractError,
+    events::{WalletDeniedEvent, EVENT_SCHEMA_VERSION},
+    state::{deny_list::*, global::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct DenyWallet<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DenyEntry::INIT_SPACE,
+        seeds = [DenyEntry::SEED_PREFIX.as_bytes(), wallet.as_ref()],
+        bump
+    )]
+    deny_entry: Account<'info, DenyEntry>,
+
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    admin: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl DenyWallet<'_> {
+    pub fn handler(ctx: Context<DenyWallet>, wallet: Pubkey, reason: u16) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        ctx.accounts.deny_entry.set_inner(DenyEntry {
+            wallet,
+            reason,
+            created_at: timestamp,
+        });
+
+        emit_cpi!(WalletDeniedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            wallet,
+            reason,
+            authority: ctx.accounts.admin.key(),
+            timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+/// Applies a queued set_params once its timelock elapsed. Permissionless, the
+/// change was already authorized when it was queued.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteParams<'info> {
+    /// Anyone, covers the rent of growing an older global.
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl ExecuteParams<'_> {
+    pub fn handler(ctx: Context<ExecuteParams>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let global = &mut ctx.accounts.global;
+        let params = global.execute_settings(timestamp)?;
+
+        emit_cpi!(ParamsExecutedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            params,
+            timestamp,
+        });
+        emit_cpi!(global.into_event());
+        Ok(())
+    }
+}
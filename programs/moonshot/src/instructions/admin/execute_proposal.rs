@@ -0,0 +1,236 @@
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{council::*, global::*, versioned::realloc_to_current, whitelist::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [Council::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    council: Box<Account<'info, Council>>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::SEED_PREFIX.as_bytes(), proposal.index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    /// CHECK: whitelist PDA of AddWl / RemoveWl actions, checked against the action
+    whitelist: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: receiver of a Withdraw action, or the global fee receiver for RemoveWl
+    receiver: Option<UncheckedAccount<'info>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.executor.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let council_key = ctx.accounts.council.key();
+        ctx.accounts.council.execute(&mut ctx.accounts.proposal)?;
+
+        let action = ctx.accounts.proposal.action.clone();
+        if !matches!(action, AdminAction::AcceptAuthority) {
+            require_keys_eq!(
+                ctx.accounts.global.global_authority,
+                council_key,
+                ContractError::CouncilNotAuthority
+            );
+        }
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        match action.clone() {
+            AdminAction::SetParams(params) => {
+                let global = &mut ctx.accounts.global;
+                if let Some(QueuedSettings {
+                    params,
+                    eta,
+                    replaced,
+                }) = global.request_settings(params, timestamp)?
+                {
+                    if let Some(params) = replaced {
+                        emit_cpi!(ParamsCancelledEvent {
+                            schema_version: EVENT_SCHEMA_VERSION,
+                            params,
+                            timestamp,
+                        });
+                    }
+                    emit_cpi!(ParamsQueuedEvent {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        params,
+                        eta,
+                        timestamp
+                    });
+                }
+                emit_cpi!(global.into_event());
+            }
+            AdminAction::Pause => {
+                let global = &mut ctx.accounts.global;
+                global.status = ProgramStatus::Paused;
+                emit_cpi!(global.into_event());
+            }
+            AdminAction::AddWl { creator } => {
+                ctx.accounts.add_wl(creator)?;
+                emit_cpi!(WhitelistAddedEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    creator,
+                    whitelist: ctx.accounts.whitelist()?.key(),
+                    authority: council_key,
+                    timestamp,
+                });
+            }
+            AdminAction::RemoveWl { creator } => {
+                ctx.accounts.remove_wl(creator)?;
+                emit_cpi!(WhitelistRemovedEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    creator,
+                    whitelist: ctx.accounts.whitelist()?.key(),
+                    authority: council_key,
+                    timestamp,
+                });
+            }
+            AdminAction::Withdraw { receiver, amount } => {
+                let remaining = ctx.accounts.withdraw(receiver, amount)?;
+                emit_cpi!(WithdrawEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    withdraw_authority: council_key,
+                    receiver,
+                    withdrawn: amount,
+                    remaining,
+                    withdraw_time: timestamp,
+                });
+            }
+            AdminAction::AcceptAuthority => {
+                let global = &mut ctx.accounts.global;
+                let previous_authority = global.global_authority;
+                global.accept_authority(&council_key)?;
+                emit_cpi!(AuthorityAcceptedEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    previous_authority,
+                    global_authority: global.global_authority,
+                    timestamp,
+                });
+            }
+            AdminAction::SetRole { role, holder } => {
+                let previous_holder = ctx.accounts.global.set_role(role, holder);
+                if holder == Pubkey::default() {
+                    emit_cpi!(RoleRevokedEvent {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        role,
+                        holder: previous_holder,
+                        timestamp,
+                    });
+                } else {
+                    emit_cpi!(RoleGrantedEvent {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        role,
+                        holder,
+                        previous_holder,
+                        timestamp,
+                    });
+                }
+            }
+            AdminAction::UpdateCouncil { signers, threshold } => {
+                let council = &mut ctx.accounts.council;
+                council.set_members(signers, threshold)?;
+                emit_cpi!(CouncilUpdateEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    council: council_key,
+                    signers: council.signers.clone(),
+                    threshold: council.threshold,
+                    generation: council.generation,
+                });
+            }
+        }
+
+        emit_cpi!(ProposalExecutedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            proposal: ctx.accounts.proposal.key(),
+            executor: ctx.accounts.executor.key(),
+            action,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    fn whitelist(&self) -> Result<AccountInfo<'info>> {
+        let whitelist = self
+            .whitelist
+            .as_ref()
+            .ok_or(ContractError::InvalidArgument)?;
+        Ok(whitelist.to_account_info())
+    }
+
+    fn add_wl(&self, creator: Pubkey) -> Result<()> {
+        Whitelist::create_entry(
+            &self.whitelist()?,
+            &self.executor.to_account_info(),
+            &self.system_program.to_account_info(),
+            creator,
+        )
+    }
+
+    fn receiver(&self, receiver: Pubkey) -> Result<AccountInfo<'info>> {
+        let receiver_account = self
+            .receiver
+            .as_ref()
+            .ok_or(ContractError::InvalidArgument)?;
+        require_keys_eq!(
+            receiver_account.key(),
+            receiver,
+            ContractError::InvalidArgument
+        );
+        Ok(receiver_account.to_account_info())
+    }
+
+    fn remove_wl(&self, creator: Pubkey) -> Result<()> {
+        // the rent goes to the protocol, not to whoever happens to execute the proposal
+        let fee_receiver = self.global.fee_receiver;
+        require_keys_neq!(
+            fee_receiver,
+            Pubkey::default(),
+            ContractError::InvalidFeeReceiver
+        );
+        Whitelist::close_entry(&self.whitelist()?, &self.receiver(fee_receiver)?, &creator)
+    }
+
+    /// Returns what the council has left to withdraw.
+    fn withdraw(&self, receiver: Pubkey, amount: u64) -> Result<u64> {
+        let receiver_account = self.receiver(receiver)?;
+
+        // the council account itself has to stay rent exempt
+        let min_balance = Rent::get()?.minimum_balance(8 + Council::INIT_SPACE);
+        let available = self.council.get_lamports().saturating_sub(min_balance);
+        require_gte!(available, amount, ContractError::InsufficientUserSOL);
+
+        self.council.sub_lamports(amount)?;
+        receiver_account.add_lamports(amount)?;
+        msg!("ExecuteProposal: withdrew {} lamports", amount);
+        Ok(available - amount)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    errors::ContractError,
+    events::{RoleGrantedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl GrantRole<'_> {
+    pub fn handler(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        require_keys_neq!(holder, Pubkey::default(), ContractError::InvalidArgument);
+        let previous_holder = ctx.accounts.global.set_role(role, holder);
+
+        emit_cpi!(RoleGrantedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            role,
+            holder,
+            previous_holder,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    errors::ContractError,
+    events::{CouncilUpdateEvent, EVENT_SCHEMA_VERSION},
+    state::{council::*, global::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitCouncil<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Council::INIT_SPACE,
+        seeds = [Council::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    council: Box<Account<'info, Council>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl InitCouncil<'_> {
+    /// Sets up the council. It takes over administration once its address is
+    /// proposed as global authority and an `AcceptAuthority` proposal executes.
+    pub fn handler(ctx: Context<InitCouncil>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        let council = &mut ctx.accounts.council;
+        council.set_members(signers, threshold)?;
+
+        emit_cpi!(CouncilUpdateEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            council: council.key(),
+            signers: council.signers.clone(),
+            threshold: council.threshold,
+            generation: council.generation,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    errors::ContractError,
+    events::{LaunchConfigEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, launch_config::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct InitLaunchConfig<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [LaunchConfig::SEED_PREFIX.as_bytes(), index.to_le_bytes().as_ref()],
+        bump,
+    )]
+    launch_config: Box<Account<'info, LaunchConfig>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl InitLaunchConfig<'_> {
+    pub fn handler(
+        ctx: Context<InitLaunchConfig>,
+        index: u16,
+        params: LaunchConfigParams,
+    ) -> Result<()> {
+        let launch_config = &mut ctx.accounts.launch_config;
+        launch_config.init(index, params, ctx.bumps.launch_config);
+        launch_config.validate(&ctx.accounts.global)?;
+
+        emit_cpi!(LaunchConfigEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            launch_config: launch_config.key(),
+            index,
+            name: launch_config.name.clone(),
+            enabled: launch_config.enabled,
+            curve: launch_config.curve,
+            fees: launch_config.fees,
+            migration: launch_config.migration,
+        });
+        Ok(())
+    }
+}
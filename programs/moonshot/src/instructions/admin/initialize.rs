@@ -1,4 +1,8 @@
-use crate::{errors::ContractError, events::*, state::global::*};
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{global::*, versioned::CURRENT_VERSION},
+};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
@@ -12,7 +16,7 @@ pub struct Initialize<'info> {
         init,
         space = 8 + Global::INIT_SPACE,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized != true @ ContractError::AlreadyInitialized,
+        constraint = !global.initialized @ ContractError::AlreadyInitialized,
         bump,
         payer = authority,
     )]
@@ -24,16 +28,17 @@ pub struct Initialize<'info> {
 impl Initialize<'_> {
     pub fn handler(ctx: Context<Initialize>, params: GlobalSettingsInput) -> Result<()> {
         let global = &mut ctx.accounts.global;
-        global.update_authority(GlobalAuthorityInput {
-            global_authority: Some(ctx.accounts.authority.key()),
-            migration_authority: Some(ctx.accounts.authority.key()),
-        });
-        global.update_settings(params.clone());
-
-        require_gt!(global.mint_decimals, 0, ContractError::InvalidArgument);
+        global.global_authority = ctx.accounts.authority.key();
+        // the deployer starts with every role, the Migrator included, and hands them out
+        // with grant_role
+        for role in Role::ALL {
+            global.set_role(role, ctx.accounts.authority.key());
+        }
+        global.update_settings(params.clone())?;
 
         global.status = ProgramStatus::Running;
         global.initialized = true;
+        global.version = CURRENT_VERSION;
         emit_cpi!(global.into_event());
         Ok(())
     }
@@ -0,0 +1,59 @@
+use crate::{
+    errors::ContractError,
+    events::{AccountMigratedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateGlobal<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    /// CHECK: read through the versioned deserializer, an older layout does not fit `Account`
+    global: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+impl MigrateGlobal<'_> {
+    pub fn handler(ctx: Context<MigrateGlobal>) -> Result<()> {
+        let global_info = ctx.accounts.global.to_account_info();
+        require_keys_eq!(*global_info.owner, crate::ID, ContractError::NotInitialized);
+
+        let global = Global::try_deserialize(&mut &global_info.try_borrow_data()?[..])?;
+        require!(global.initialized, ContractError::NotInitialized);
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            global.global_authority,
+            ContractError::InvalidGlobalAuthority
+        );
+
+        let old_len = realloc_to_current::<Global>(
+            &global_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let from_version = if old_len == 8 + Global::V1_SPACE {
+            1
+        } else {
+            global.version
+        };
+        global.try_serialize(&mut &mut global_info.try_borrow_mut_data()?[..])?;
+
+        emit_cpi!(AccountMigratedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            account: global_info.key(),
+            from_version,
+            to_version: global.version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -2,3 +2,26 @@ pub mod initialize;
 pub mod set_params;
 pub mod add_wl;
 pub mod remove_wl;
+pub mod update_wl;
+pub mod add_wl_batch;
+pub mod remove_wl_batch;
+pub mod deny_wallet;
+pub mod allow_wallet;
+pub mod set_deny_list;
+pub mod set_wl_root;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod cancel_authority;
+pub mod init_council;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod execute_proposal;
+pub mod execute_params;
+pub mod cancel_params;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod pause;
+pub mod set_fees;
+pub mod migrate_global;
+pub mod init_launch_config;
+pub mod update_launch_config;
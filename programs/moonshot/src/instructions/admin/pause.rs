@@ -0,0 +1,42 @@
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+/// Emergency stop, not subject to the params timelock. Resuming goes through set_params.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Pauser, &pauser.key()) @ ContractError::MissingRole
+    )]
+    pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl Pause<'_> {
+    pub fn handler(ctx: Context<Pause>) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.pauser.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+        global.status = ProgramStatus::Paused;
+
+        emit_cpi!(global.into_event());
+        Ok(())
+    }
+}
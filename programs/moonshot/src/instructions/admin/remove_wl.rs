@@ -1,15 +1,16 @@
-use anchor_lang::prelude::*;
 use crate::{
-    state::{global::*, whitelist::*},
     errors::ContractError,
+    events::{WhitelistRemovedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, whitelist::*},
 };
+use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveWl<'info> {
     #[account(
-        mut,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
@@ -23,9 +24,28 @@ pub struct RemoveWl<'info> {
     pub whitelist: Account<'info, Whitelist>,
 
     #[account(
-        mut, 
-        constraint = admin.key() == global.global_authority.key() @ ContractError::InvalidGlobalAuthority
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
     )]
     pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
+
+impl RemoveWl<'_> {
+    /// Closes the entry, its rent goes back to the admin. Pre-quota entries load too.
+    pub fn handler(ctx: Context<RemoveWl>) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
+        let whitelist = &ctx.accounts.whitelist;
+        emit_cpi!(WhitelistRemovedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            creator: whitelist.creator,
+            whitelist: whitelist.key(),
+            authority: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    errors::ContractError,
+    events::{RoleRevokedEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl RevokeRole<'_> {
+    pub fn handler(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let holder = ctx.accounts.global.set_role(role, Pubkey::default());
+        require_keys_neq!(holder, Pubkey::default(), ContractError::MissingRole);
+
+        emit_cpi!(RoleRevokedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            role,
+            holder,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    errors::ContractError,
+    events::{DenyListToggledEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+/// Turns the swap deny list check on or off. Clients have to pass the trader's
+/// deny entry PDA while it is on.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDenyList<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl SetDenyList<'_> {
+    pub fn handler(ctx: Context<SetDenyList>, enabled: bool) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+        global.status.require(ProgramAction::Whitelist)?;
+        global.deny_list_enabled = enabled;
+
+        emit_cpi!(DenyListToggledEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            enabled,
+            authority: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    errors::ContractError,
+    events::*,
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct FeeSettingsInput {
+    pub migrate_fee_amount: Option<u64>,
+    pub keeper_reward_amount: Option<u64>,
+    pub fee_receiver: Option<Pubkey>,
+    pub lp_fee_creator_bps: Option<u64>,
+}
+
+impl From<FeeSettingsInput> for GlobalSettingsInput {
+    fn from(fees: FeeSettingsInput) -> Self {
+        GlobalSettingsInput {
+            migrate_fee_amount: fees.migrate_fee_amount,
+            keeper_reward_amount: fees.keeper_reward_amount,
+            fee_receiver: fees.fee_receiver,
+            lp_fee_creator_bps: fees.lp_fee_creator_bps,
+            ..Default::default()
+        }
+    }
+}
+
+/// Fee subset of set_params for the fee manager, timelocked the same way.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::FeeManager, &fee_manager.key()) @ ContractError::MissingRole
+    )]
+    fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl SetFees<'_> {
+    pub fn handler(ctx: Context<SetFees>, fees: FeeSettingsInput) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.fee_manager.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        if let Some(QueuedSettings {
+            params,
+            eta,
+            replaced,
+        }) = global.request_settings(fees.into(), timestamp)?
+        {
+            if let Some(params) = replaced {
+                emit_cpi!(ParamsCancelledEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    params,
+                    timestamp,
+                });
+            }
+            emit_cpi!(ParamsQueuedEvent {
+                schema_version: EVENT_SCHEMA_VERSION,
+                params,
+                eta,
+                timestamp
+            });
+        }
+
+        emit_cpi!(global.into_event());
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     errors::ContractError,
     events::*,
-    state::global::*,
+    state::{global::*, versioned::realloc_to_current},
 };
 use anchor_lang::prelude::*;
 
@@ -17,42 +17,44 @@ pub struct SetParams<'info> {
     #[account(
         mut,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
 
-    #[account()]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    new_authority: Option<UncheckedAccount<'info>>,
-
-    #[account()]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    new_migration_authority: Option<UncheckedAccount<'info>>,
-
     system_program: Program<'info, System>,
 }
 
 impl SetParams<'_> {
     pub fn handler(ctx: Context<SetParams>, params: GlobalSettingsInput) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
         let global = &mut ctx.accounts.global;
 
-        global.update_authority(GlobalAuthorityInput {
-            global_authority: if let Some(new_authority) = ctx.accounts.new_authority.as_ref() {
-                Some(*new_authority.key)
-            } else {
-                None
-            },
-            
-            migration_authority: if let Some(new_migration_authority) =
-                ctx.accounts.new_migration_authority.as_ref()
-            {
-                Some(*new_migration_authority.key)
-            } else {
-                None
-            },
-        });
-        global.update_settings(params.clone());
+        let timestamp = Clock::get()?.unix_timestamp;
+        if let Some(QueuedSettings {
+            params,
+            eta,
+            replaced,
+        }) = global.request_settings(params, timestamp)?
+        {
+            if let Some(params) = replaced {
+                emit_cpi!(ParamsCancelledEvent {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    params,
+                    timestamp,
+                });
+            }
+            emit_cpi!(ParamsQueuedEvent {
+                schema_version: EVENT_SCHEMA_VERSION,
+                params,
+                eta,
+                timestamp
+            });
+        }
 
         emit_cpi!(global.into_event());
 
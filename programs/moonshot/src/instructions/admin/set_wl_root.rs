@@ -0,0 +1,49 @@
+use crate::{
+    errors::ContractError,
+    events::{WhitelistRootEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+/// Replaces the creator merkle root. Whitelist PDAs keep working alongside it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetWlRoot<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl SetWlRoot<'_> {
+    pub fn handler(ctx: Context<SetWlRoot>, root: [u8; 32]) -> Result<()> {
+        realloc_to_current::<Global>(
+            &ctx.accounts.global.to_account_info(),
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let global = &mut ctx.accounts.global;
+        global.status.require(ProgramAction::Whitelist)?;
+        let previous_root = std::mem::replace(&mut global.creator_merkle_root, root);
+
+        emit_cpi!(WhitelistRootEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            root,
+            previous_root,
+            authority: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
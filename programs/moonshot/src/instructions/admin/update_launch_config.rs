@@ -0,0 +1,51 @@
+use crate::{
+    errors::ContractError,
+    events::{LaunchConfigEvent, EVENT_SCHEMA_VERSION},
+    state::{global::*, launch_config::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLaunchConfig<'info> {
+    #[account(
+        constraint = authority.key() == global.global_authority @ ContractError::InvalidGlobalAuthority
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [LaunchConfig::SEED_PREFIX.as_bytes(), launch_config.index.to_le_bytes().as_ref()],
+        bump = launch_config.bump,
+    )]
+    launch_config: Box<Account<'info, LaunchConfig>>,
+}
+
+impl UpdateLaunchConfig<'_> {
+    /// Curve and fee changes apply to curves created afterwards, the migration
+    /// settings also to live curves of this config.
+    pub fn handler(ctx: Context<UpdateLaunchConfig>, input: LaunchConfigInput) -> Result<()> {
+        let launch_config = &mut ctx.accounts.launch_config;
+        launch_config.update(input);
+        launch_config.validate(&ctx.accounts.global)?;
+
+        emit_cpi!(LaunchConfigEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            launch_config: launch_config.key(),
+            index: launch_config.index,
+            name: launch_config.name.clone(),
+            enabled: launch_config.enabled,
+            curve: launch_config.curve,
+            fees: launch_config.fees,
+            migration: launch_config.migration,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    errors::ContractError,
+    state::{global::*, versioned::realloc_to_current, whitelist::*},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateWl<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [Whitelist::SEED_PREFIX.as_bytes(), whitelist.creator.key().as_ref()],
+        bump
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+
+    #[account(
+        mut,
+        constraint = global.has_role(Role::WhitelistManager, &admin.key()) @ ContractError::MissingRole
+    )]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl UpdateWl<'_> {
+    /// Replaces the entry's quota, expiry and tier. The launch count is kept.
+    pub fn handler(ctx: Context<UpdateWl>, input: WhitelistInput) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Whitelist)?;
+        realloc_to_current::<Whitelist>(
+            &ctx.accounts.whitelist.to_account_info(),
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        ctx.accounts.whitelist.update(input);
+
+        let whitelist = &ctx.accounts.whitelist;
+        emit_cpi!(whitelist.updated_event(
+            whitelist.key(),
+            ctx.accounts.admin.key(),
+            Clock::get()?.unix_timestamp,
+        ));
+        Ok(())
+    }
+}
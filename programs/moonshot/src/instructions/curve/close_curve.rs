@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ContractError,
+    state::{bonding_curve::*, global::*, versioned::realloc_to_current},
+};
+
+/// Closes a refunding curve once its holders sold every token back. What is left above
+/// rent goes out with sweep_curve_remainder.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseCurve<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Pauser, &pauser.key()) @ ContractError::MissingRole
+    )]
+    pauser: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    /// CHECK: only used to derive the bonding curve
+    mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    system_program: Program<'info, System>,
+}
+
+impl CloseCurve<'_> {
+    pub fn handler(ctx: Context<CloseCurve>) -> Result<()> {
+        realloc_to_current::<BondingCurve>(
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.pauser.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let status_event = ctx
+            .accounts
+            .bonding_curve
+            .close_refunded(&ctx.accounts.global, Clock::get()?.unix_timestamp)?;
+
+        emit_cpi!(status_event);
+        Ok(())
+    }
+}
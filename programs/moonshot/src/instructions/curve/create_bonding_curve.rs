@@ -6,9 +6,14 @@ use anchor_spl::{
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
-use crate::state::{bonding_curve::*, global::*, whitelist::*};
+use crate::state::{
+    bonding_curve::*, global::*, launch_config::*, versioned::realloc_to_current, whitelist::*,
+};
 
-use crate::{errors::ContractError, events::CreateEvent};
+use crate::{
+    errors::ContractError,
+    events::{CreateEvent, EVENT_SCHEMA_VERSION},
+};
 
 use crate::state::bonding_curve::locker::{BondingCurveLockerCtx, IntoBondingCurveLockerCtx};
 
@@ -19,7 +24,7 @@ pub struct CreateBondingCurve<'info> {
     #[account(
         init,
         payer = creator,
-        mint::decimals = global.mint_decimals,
+        mint::decimals = launch_config.as_ref().map_or(global.mint_decimals, |c| c.curve.mint_decimals),
         mint::authority = bonding_curve,
         mint::freeze_authority = bonding_curve
     )]
@@ -46,17 +51,23 @@ pub struct CreateBondingCurve<'info> {
 
     #[account(
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
-        constraint = global.status == ProgramStatus::Running @ ContractError::ProgramNotRunning,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
 
     #[account(
+        mut,
         seeds = [Whitelist::SEED_PREFIX.as_bytes(), creator.key().as_ref()],
         bump,
     )]
-    whitelist: Option<UncheckedAccount<'info>>,
+    whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    #[account(
+        seeds = [LaunchConfig::SEED_PREFIX.as_bytes(), params.launch_config.unwrap_or_default().to_le_bytes().as_ref()],
+        bump = launch_config.bump,
+    )]
+    launch_config: Option<Box<Account<'info, LaunchConfig>>>,
 
     #[account(mut)]
     ///CHECK: Using seed to validate metadata account
@@ -92,6 +103,15 @@ impl<'info> IntoBondingCurveLockerCtx<'info> for CreateBondingCurve<'info> {
 
 impl CreateBondingCurve<'_> {
     pub fn validate(&self, params: &CreateBondingCurveParams) -> Result<()> {
+        self.global.status.require(ProgramAction::CreateCurve)?;
+        // the index picks the config, the account has to come with it
+        require!(
+            params.launch_config.is_some() == self.launch_config.is_some(),
+            ContractError::InvalidLaunchConfig
+        );
+        if let Some(launch_config) = &self.launch_config {
+            require!(launch_config.enabled, ContractError::LaunchConfigDisabled);
+        }
         let clock = Clock::get()?;
         // validate start time
         if let Some(start_time) = params.start_time {
@@ -109,14 +129,35 @@ impl CreateBondingCurve<'_> {
     ) -> Result<()> {
         let global = ctx.accounts.global.clone();
         let clock = Clock::get()?;
+        if let Some(whitelist) = &ctx.accounts.whitelist {
+            // entries from before quotas are grown before they are written back
+            realloc_to_current::<Whitelist>(
+                &whitelist.to_account_info(),
+                &ctx.accounts.creator.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
         if global.whitelist_enabled {
-            let whitelist = ctx.accounts.whitelist.is_some();
-            require!(whitelist, ContractError::NotWhiteList);
+            // either the per-creator PDA or a proof against the merkle root
+            match (&params.whitelist_proof, &mut ctx.accounts.whitelist) {
+                (Some(proof), _) => require!(
+                    global.creator_in_merkle_whitelist(&ctx.accounts.creator.key(), proof),
+                    ContractError::NotWhiteList
+                ),
+                (None, Some(whitelist)) => {
+                    whitelist.use_launch(params.launch_config, clock.unix_timestamp)?
+                }
+                (None, None) => return Err(ContractError::NotWhiteList.into()),
+            }
         }
+        let terms = match &ctx.accounts.launch_config {
+            Some(launch_config) => launch_config.launch_terms(launch_config.key()),
+            None => global.launch_terms(),
+        };
         ctx.accounts.bonding_curve.update_from_params(
             ctx.accounts.mint.key(),
             ctx.accounts.creator.key(),
-            &ctx.accounts.global,
+            &terms,
             &params,
             &clock,
             ctx.bumps.bonding_curve,
@@ -151,9 +192,18 @@ impl CreateBondingCurve<'_> {
         locker.revoke_mint_authority()?;
         locker.lock_ata()?;
 
+        let status_event = ctx
+            .accounts
+            .bonding_curve
+            .transition(CurveStatus::Trading, clock.unix_timestamp)?;
+        let locker = &mut ctx
+            .accounts
+            .into_bonding_curve_locker_ctx(ctx.bumps.bonding_curve);
         BondingCurve::invariant(locker)?;
-        let bonding_curve = ctx.accounts.bonding_curve.as_mut();
-        emit!(CreateEvent {
+        emit_cpi!(status_event);
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        emit_cpi!(CreateEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             name: params.name,
             symbol: params.symbol,
             uri: params.uri,
@@ -165,6 +215,7 @@ impl CreateBondingCurve<'_> {
             real_sol_reserves: bonding_curve.real_sol_reserves,
             real_token_reserves: bonding_curve.real_token_reserves,
             start_time: bonding_curve.start_time,
+            launch_config: bonding_curve.launch_config,
         });
         msg!("CreateBondingCurve::handler: success");
         Ok(())
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    errors::ContractError,
+    events::{AccountMigratedEvent, EVENT_SCHEMA_VERSION},
+    state::{bonding_curve::*, versioned::*},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateCurve<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    /// CHECK: read through the versioned deserializer, an older layout does not fit `Account`
+    bonding_curve: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+impl MigrateCurve<'_> {
+    /// Permissionless, the payer only covers the extra rent of the larger layout.
+    pub fn handler(ctx: Context<MigrateCurve>) -> Result<()> {
+        let curve_info = ctx.accounts.bonding_curve.to_account_info();
+        require_keys_eq!(
+            *curve_info.owner,
+            crate::ID,
+            ContractError::NotBondingCurveMint
+        );
+
+        let bonding_curve = BondingCurve::try_deserialize(&mut &curve_info.try_borrow_data()?[..])?;
+        let old_len = realloc_to_current::<BondingCurve>(
+            &curve_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let from_version = if old_len == 8 + BondingCurve::V1_SPACE {
+            1
+        } else {
+            bonding_curve.version
+        };
+        bonding_curve.try_serialize(&mut &mut curve_info.try_borrow_mut_data()?[..])?;
+
+        emit_cpi!(AccountMigratedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            account: curve_info.key(),
+            from_version,
+            to_version: bonding_curve.version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
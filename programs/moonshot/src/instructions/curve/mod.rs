@@ -1,3 +1,7 @@
 pub mod create_bonding_curve;
 pub mod swap;
+pub mod set_curve_flags;
+pub mod migrate_curve;
+pub mod refund_curve;
+pub mod close_curve;
 pub use create_bonding_curve::*;
\ No newline at end of file
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    errors::ContractError,
+    events::{CurveFlagsEvent, EVENT_SCHEMA_VERSION},
+    state::{bonding_curve::*, global::*, versioned::realloc_to_current},
+};
+
+use crate::state::bonding_curve::locker::{BondingCurveLockerCtx, IntoBondingCurveLockerCtx};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CurveFlagsParams {
+    pub paused: Option<bool>,
+    pub delisted: Option<bool>,
+    pub reason: u16, // off-chain reason code, only carried by the event
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCurveFlags<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Pauser, &pauser.key()) @ ContractError::MissingRole
+    )]
+    pauser: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+
+    token_program: Program<'info, Token>,
+
+    system_program: Program<'info, System>,
+}
+
+impl<'info> IntoBondingCurveLockerCtx<'info> for SetCurveFlags<'info> {
+    fn into_bonding_curve_locker_ctx(
+        &self,
+        bonding_curve_bump: u8,
+    ) -> BondingCurveLockerCtx<'info> {
+        BondingCurveLockerCtx {
+            bonding_curve_bump,
+            mint: self.mint.clone(),
+            global: self.global.clone(),
+            bonding_curve: self.bonding_curve.clone(),
+            bonding_curve_token_account: self.bonding_curve_token_account.clone(),
+            token_program: self.token_program.clone(),
+        }
+    }
+}
+
+impl SetCurveFlags<'_> {
+    pub fn handler(ctx: Context<SetCurveFlags>, params: CurveFlagsParams) -> Result<()> {
+        realloc_to_current::<BondingCurve>(
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.pauser.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let lifted = ctx
+            .accounts
+            .bonding_curve
+            .set_halt_flags(params.paused, params.delisted);
+
+        // a curve only resumes trading from a consistent state
+        if lifted && ctx.accounts.bonding_curve.status.is_active() {
+            BondingCurve::invariant(
+                &mut ctx
+                    .accounts
+                    .into_bonding_curve_locker_ctx(ctx.bumps.bonding_curve),
+            )?;
+        }
+
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        emit_cpi!(CurveFlagsEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: ctx.accounts.mint.key(),
+            paused: bonding_curve.paused,
+            delisted: bonding_curve.delisted,
+            reason: params.reason,
+            authority: ctx.accounts.pauser.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, system_instruction},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, Transfer},
@@ -7,7 +10,11 @@ use anchor_spl::{
 use crate::{
     errors::ContractError,
     events::*,
-    state::{bonding_curve::*, global::*},
+    state::{
+        bonding_curve::*, deny_list::DenyEntry, global::*, meteora::*,
+        versioned::realloc_to_current,
+    },
+    util::{token_balance, BASIS_POINTS_DIVISOR},
 };
 
 use crate::state::bonding_curve::locker::{BondingCurveLockerCtx, IntoBondingCurveLockerCtx};
@@ -28,7 +35,7 @@ pub struct Swap<'info> {
 
     #[account(
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
@@ -42,7 +49,6 @@ pub struct Swap<'info> {
     #[account(
         mut,
         seeds = [BondingCurve::SEED_PREFIX.as_bytes(), mint.to_account_info().key.as_ref()],
-        constraint = bonding_curve.complete == false @ ContractError::BondingCurveComplete,
         bump,
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
@@ -63,12 +69,19 @@ pub struct Swap<'info> {
     user_token_account: Box<Account<'info, TokenAccount>>,
 
     system_program: Program<'info, System>,
-    
+
     token_program: Program<'info, Token>,
 
     associated_token_program: Program<'info, AssociatedToken>,
 
     clock: Sysvar<'info, Clock>,
+
+    #[account(
+        seeds = [DenyEntry::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: the user's deny list PDA, required while the deny list is enabled
+    deny_entry: Option<UncheckedAccount<'info>>,
 }
 impl<'info> IntoBondingCurveLockerCtx<'info> for Swap<'info> {
     fn into_bonding_curve_locker_ctx(
@@ -88,16 +101,29 @@ impl<'info> IntoBondingCurveLockerCtx<'info> for Swap<'info> {
 impl Swap<'_> {
     pub fn validate(&self, params: &SwapParams) -> Result<()> {
         let SwapParams {
-            base_in: _,
+            base_in,
             exact_in_amount,
             min_out_amount: _,
         } = params;
         let clock = Clock::get()?;
 
-        require!(
-            self.bonding_curve.is_started(&clock),
-            ContractError::CurveNotStarted
-        );
+        self.global.status.require(ProgramAction::Swap)?;
+        DenyEntry::require_allowed(
+            &self.global,
+            self.deny_entry.as_ref().map(|entry| entry.as_ref()),
+        )?;
+        self.bonding_curve.require_not_halted()?;
+        // migrated curves trade against their pool, refunding ones buy tokens back
+        let action = self.bonding_curve.status.swap_action();
+        self.bonding_curve.status.require(action)?;
+        match action {
+            CurveAction::Swap => require!(
+                self.bonding_curve.is_started(&clock),
+                ContractError::CurveNotStarted
+            ),
+            CurveAction::Refund => require!(*base_in, ContractError::RefundSellOnly),
+            _ => {}
+        }
         require!(exact_in_amount > &0, ContractError::MinSwap);
 
         require!(
@@ -106,7 +132,28 @@ impl Swap<'_> {
         );
         Ok(())
     }
-    pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+    pub fn handler<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        params: SwapParams,
+    ) -> Result<()> {
+        // curves created before the version field are grown before their first write
+        realloc_to_current::<BondingCurve>(
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        // routed trades are counted too, so the sequence has no gaps across venues
+        let trade_seq = ctx.accounts.bonding_curve.next_trade()?;
+
+        if ctx.accounts.bonding_curve.status.swap_action() == CurveAction::RoutedSwap {
+            return Swap::route_to_pool(ctx, params, trade_seq);
+        }
+        let price_before = ctx
+            .accounts
+            .bonding_curve
+            .marginal_price()
+            .ok_or(ContractError::ArithmeticError)?;
+
         let SwapParams {
             base_in,
             exact_in_amount,
@@ -128,6 +175,7 @@ impl Swap<'_> {
         let sol_amount: u64;
         let token_amount: u64;
         let fee_lamports: u64;
+        let mut completed: Option<CurveStatusEvent> = None;
 
         if base_in {
             // Sell tokens
@@ -170,6 +218,13 @@ impl Swap<'_> {
             msg!("Fee: {} lamports", fee_lamports);
 
             Swap::complete_buy(&ctx, buy_result.clone(), min_out_amount, fee_lamports)?;
+            if buy_result.completes {
+                completed = Some(
+                    ctx.accounts
+                        .bonding_curve
+                        .transition(CurveStatus::Complete, clock.unix_timestamp)?,
+                );
+            }
         }
 
         BondingCurve::invariant(
@@ -178,25 +233,28 @@ impl Swap<'_> {
                 .into_bonding_curve_locker_ctx(ctx.bumps.bonding_curve),
         )?;
         let bonding_curve = &ctx.accounts.bonding_curve;
+        let clock = Clock::get()?;
+        // the whole swap fee goes to the fee receiver
+        let fees = vec![FeeShare {
+            recipient: ctx.accounts.fee_receiver.key(),
+            lamports: fee_lamports,
+        }];
 
         // Emit trade event used for indexing
-        emit_cpi!(TradeEvent {
-            mint: *ctx.accounts.mint.to_account_info().key,
-            sol_amount: sol_amount,
-            token_amount: token_amount,
-            fee_lamports: fee_lamports,
-            is_buy: !base_in,
-            user: *ctx.accounts.user.to_account_info().key,
-            timestamp: Clock::get()?.unix_timestamp,
-            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
-            virtual_token_reserves: bonding_curve.virtual_token_reserves,
-            real_sol_reserves: bonding_curve.real_sol_reserves,
-            real_token_reserves: bonding_curve.real_token_reserves,
-        });
+        emit_cpi!(bonding_curve.trade_event(
+            &ctx.accounts.global,
+            ctx.accounts.user.key(),
+            !base_in,
+            [sol_amount, token_amount],
+            price_before,
+            fees,
+        )?);
 
         // Emit complete event when bonding curve is completed
-        if bonding_curve.complete {
+        if let Some(status_event) = completed {
+            emit_cpi!(status_event);
             emit_cpi!(CompleteEvent {
+                schema_version: EVENT_SCHEMA_VERSION,
                 user: *ctx.accounts.user.to_account_info().key,
                 mint: *ctx.accounts.mint.to_account_info().key,
                 virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
@@ -204,6 +262,8 @@ impl Swap<'_> {
                 real_sol_reserves: bonding_curve.real_sol_reserves,
                 real_token_reserves: bonding_curve.real_token_reserves,
                 timestamp: Clock::get()?.unix_timestamp,
+                trade_seq,
+                slot: clock.slot,
             });
         }
 
@@ -212,13 +272,189 @@ impl Swap<'_> {
         Ok(())
     }
 
+    /// Forwards the trade to the migrated pool.
+    ///
+    /// Remaining accounts: `[user_quote_token, pool, a_vault, b_vault, a_token_vault,
+    /// b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp,
+    /// protocol_token_fee, vault_program, meteora_program]`, `protocol_token_fee` being the
+    /// pool fee account of the input token. `user_quote_token` is a wSOL account of the user:
+    /// buys wrap the SOL spent into it, sells close it so the proceeds arrive as SOL.
+    pub fn route_to_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        params: SwapParams,
+        trade_seq: u64,
+    ) -> Result<()> {
+        let SwapParams {
+            base_in,
+            exact_in_amount,
+            min_out_amount,
+        } = params;
+        msg!(
+            "Swap routed to pool. BaseIn: {}, AmountIn: {}, MinOutAmount: {}",
+            base_in,
+            exact_in_amount,
+            min_out_amount
+        );
+
+        let (user_quote_token, pool_accounts) = split_route_accounts(
+            ctx.remaining_accounts,
+            &ctx.accounts.bonding_curve.pool,
+            ctx.accounts.user.key,
+        )?;
+        let meteora_program = &pool_accounts[SWAP_METEORA_PROGRAM_INDEX];
+        let clock = Clock::get()?;
+        let (sol_reserves_before, token_reserves_before) =
+            read_pool_reserves(pool_accounts, clock.unix_timestamp)?;
+        let pool_fees = PoolFees::read(&pool_accounts[SWAP_POOL_INDEX].try_borrow_data()?)
+            .ok_or(ContractError::InvalidPool)?;
+
+        let user_token_account = ctx.accounts.user_token_account.to_account_info();
+        let (source, destination) = if base_in {
+            require!(
+                ctx.accounts.user_token_account.amount >= exact_in_amount,
+                ContractError::InsufficientUserTokens,
+            );
+            (user_token_account, user_quote_token.clone())
+        } else {
+            require!(
+                ctx.accounts.user.get_lamports() >= exact_in_amount,
+                ContractError::InsufficientUserSOL,
+            );
+            // wrap the SOL being spent
+            anchor_lang::solana_program::program::invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.user.key,
+                    user_quote_token.key,
+                    exact_in_amount,
+                ),
+                &[
+                    ctx.accounts.user.to_account_info(),
+                    user_quote_token.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            token::sync_native(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::SyncNative {
+                    account: user_quote_token.clone(),
+                },
+            ))?;
+            (user_quote_token.clone(), user_token_account)
+        };
+
+        let destination_before = token_balance(&destination)?;
+        let pool_keys: Vec<Pubkey> = pool_accounts.iter().map(|acc| acc.key()).collect();
+        let instruction = Instruction {
+            program_id: meteora_program.key(),
+            accounts: get_swap_accounts(
+                &pool_keys,
+                source.key(),
+                destination.key(),
+                ctx.accounts.user.key(),
+                ctx.accounts.token_program.key(),
+            ),
+            data: get_swap_ix_data(exact_in_amount, min_out_amount),
+        };
+        let mut account_infos = vec![source, destination.clone()];
+        account_infos.extend(pool_accounts.iter().cloned());
+        account_infos.push(ctx.accounts.user.to_account_info());
+        account_infos.push(ctx.accounts.token_program.to_account_info());
+        anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+
+        let amount_out = routed_amount_out(
+            destination_before,
+            token_balance(&destination)?,
+            min_out_amount,
+        )?;
+        msg!("Routed swap out: {}", amount_out);
+
+        if base_in {
+            // unwrap the proceeds
+            token::close_account(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::CloseAccount {
+                    account: user_quote_token.clone(),
+                    destination: ctx.accounts.user.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ))?;
+        }
+
+        let (sol_amount, token_amount) = if base_in {
+            (amount_out, exact_in_amount)
+        } else {
+            (exact_in_amount, amount_out)
+        };
+        let (sol_reserves, token_reserves) =
+            read_pool_reserves(pool_accounts, clock.unix_timestamp)?;
+
+        // the pool charges the input token, sells are valued at the pre-trade pool price
+        let (lp_fee, protocol_fee) = pool_fees
+            .trade_fees(exact_in_amount)
+            .ok_or(ContractError::ArithmeticError)?;
+        let to_lamports = |amount: u64| -> Option<u64> {
+            if !base_in {
+                return Some(amount);
+            }
+            (amount as u128)
+                .checked_mul(sol_reserves_before as u128)?
+                .checked_div(token_reserves_before as u128)?
+                .try_into()
+                .ok()
+        };
+        let fees = vec![
+            FeeShare {
+                recipient: pool_accounts[SWAP_POOL_INDEX].key(),
+                lamports: to_lamports(lp_fee).ok_or(ContractError::ArithmeticError)?,
+            },
+            FeeShare {
+                recipient: pool_accounts[SWAP_PROTOCOL_FEE_INDEX].key(),
+                lamports: to_lamports(protocol_fee).ok_or(ContractError::ArithmeticError)?,
+            },
+        ];
+        let fee_lamports = fees
+            .iter()
+            .try_fold(0u64, |sum, share| sum.checked_add(share.lamports))
+            .ok_or(ContractError::ArithmeticError)?;
+
+        emit_cpi!(TradeEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: ctx.accounts.mint.key(),
+            sol_amount,
+            token_amount,
+            fee_lamports,
+            is_buy: !base_in,
+            user: ctx.accounts.user.key(),
+            timestamp: clock.unix_timestamp,
+            virtual_sol_reserves: sol_reserves,
+            virtual_token_reserves: token_reserves,
+            real_sol_reserves: sol_reserves,
+            real_token_reserves: token_reserves,
+            venue: TradeVenue::Pool,
+            trade_seq,
+            slot: clock.slot,
+            price_before: price_from_reserves(sol_reserves_before, token_reserves_before)
+                .ok_or(ContractError::ArithmeticError)?,
+            price_after: price_from_reserves(sol_reserves, token_reserves)
+                .ok_or(ContractError::ArithmeticError)?,
+            market_cap_lamports: market_cap_from_reserves(
+                sol_reserves,
+                token_reserves,
+                ctx.accounts.mint.supply,
+            )
+            .ok_or(ContractError::ArithmeticError)?,
+            progress_bps: BASIS_POINTS_DIVISOR,
+            fees,
+        });
+        Ok(())
+    }
+
     pub fn complete_buy(
         ctx: &Context<Swap>,
         buy_result: BuyResult,
         min_out_amount: u64,
         fee_lamports: u64,
     ) -> Result<()> {
-
         let bonding_curve = &ctx.accounts.bonding_curve;
 
         // Buy tokens
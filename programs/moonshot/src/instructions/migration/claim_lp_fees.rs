@@ -0,0 +1,262 @@
+use crate::constants::{METEORA_PROGRAM_KEY, QUOTE_MINT, VAULT_SEED};
+use crate::errors::ContractError;
+use crate::events::{ClaimLpFeesEvent, EVENT_SCHEMA_VERSION};
+use crate::state::bonding_curve::{BondingCurve, CurveAction};
+use crate::state::global::{Global, ProgramAction};
+use crate::state::meteora::{
+    get_claim_fee_accounts, get_claim_fee_ix_data, get_lock_escrow_address, get_lp_mint_address,
+    LpFeePayouts, LpFeeRecipient, LpFeeSide,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use std::str::FromStr;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), token_b_mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    /// CHECK: Vault account, owns the lock escrow and collects the claimed fees before the split
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.key() == bonding_curve.pool @ ContractError::InvalidPool
+    )]
+    /// CHECK: Pool account recorded at migration
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut, address = get_lp_mint_address(pool.key) @ ContractError::InvalidPool)]
+    /// CHECK: lp mint of `pool`
+    pub lp_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_lock_escrow_address(pool.key, vault.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: lock escrow owned by the vault
+    pub lock_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(vault.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Source tokens, the vault ATA for `lp_mint` the LP was locked from
+    pub source_tokens: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(lock_escrow.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Escrow vault, the lock escrow ATA for `lp_mint`
+    pub escrow_vault: UncheckedAccount<'info>,
+
+    #[account(address = Pubkey::from_str(QUOTE_MINT).unwrap() @ ContractError::NotSOL)]
+    pub token_a_mint: Box<Account<'info, Mint>>,
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Vault accounts for token A
+    pub a_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Vault accounts for token B
+    pub b_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token vault for token A
+    pub a_token_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token vault for token B
+    pub b_token_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Vault LP mint for token A
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Vault LP mint for token B
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token A LP
+    pub a_vault_lp: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token B LP
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = token_a_mint, token::authority = vault)]
+    pub vault_token_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_b_mint, token::authority = vault)]
+    pub vault_token_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = token_a_mint, token::authority = global.fee_receiver)]
+    pub protocol_token_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_b_mint, token::authority = global.fee_receiver)]
+    pub protocol_token_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = token_a_mint, token::authority = bonding_curve.creator)]
+    pub creator_token_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_b_mint, token::authority = bonding_curve.creator)]
+    pub creator_token_b: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Vault program account
+    pub vault_program: UncheckedAccount<'info>,
+
+    #[account(address = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap())]
+    /// CHECK: Meteora Program
+    pub meteora_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimLpFees<'info> {
+    pub fn handler(ctx: Context<ClaimLpFees>) -> Result<()> {
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Withdraw)?;
+        ctx.accounts
+            .bonding_curve
+            .status
+            .require(CurveAction::ClaimLpFees)?;
+
+        let vault_bump = ctx.bumps.vault.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, vault_bump.as_ref()]];
+
+        let before = [
+            ctx.accounts.vault_token_a.amount,
+            ctx.accounts.vault_token_b.amount,
+        ];
+
+        ctx.accounts.claim_fee(signer_seeds)?;
+
+        ctx.accounts.vault_token_a.reload()?;
+        ctx.accounts.vault_token_b.reload()?;
+        let after = [
+            ctx.accounts.vault_token_a.amount,
+            ctx.accounts.vault_token_b.amount,
+        ];
+        let payouts = LpFeePayouts::from_claim(&ctx.accounts.global, before, after)
+            .ok_or(ContractError::ArithmeticError)?;
+        msg!("ClaimLpFees: {:?}", payouts);
+
+        let accounts = &ctx.accounts;
+        for (side, recipient, amount) in payouts.transfers() {
+            let (from, to) = match (side, recipient) {
+                (LpFeeSide::TokenA, LpFeeRecipient::Protocol) => {
+                    (&accounts.vault_token_a, &accounts.protocol_token_a)
+                }
+                (LpFeeSide::TokenA, LpFeeRecipient::Creator) => {
+                    (&accounts.vault_token_a, &accounts.creator_token_a)
+                }
+                (LpFeeSide::TokenB, LpFeeRecipient::Protocol) => {
+                    (&accounts.vault_token_b, &accounts.protocol_token_b)
+                }
+                (LpFeeSide::TokenB, LpFeeRecipient::Creator) => {
+                    (&accounts.vault_token_b, &accounts.creator_token_b)
+                }
+            };
+            accounts.pay_out(from, to, amount, signer_seeds)?;
+        }
+
+        emit_cpi!(ClaimLpFeesEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: ctx.accounts.token_b_mint.key(),
+            lock_escrow: ctx.accounts.lock_escrow.key(),
+            protocol_token_a_amount: payouts.protocol_token_a,
+            protocol_token_b_amount: payouts.protocol_token_b,
+            creator_token_a_amount: payouts.creator_token_a,
+            creator_token_b_amount: payouts.creator_token_b,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn claim_fee(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let accounts = get_claim_fee_accounts(
+            self.pool.key(),
+            self.lp_mint.key(),
+            self.lock_escrow.key(),
+            self.vault.key(),
+            self.source_tokens.key(),
+            [
+                self.a_token_vault.key(),
+                self.b_token_vault.key(),
+                self.a_vault.key(),
+                self.b_vault.key(),
+                self.a_vault_lp.key(),
+                self.b_vault_lp.key(),
+                self.a_vault_lp_mint.key(),
+                self.b_vault_lp_mint.key(),
+                self.vault_program.key(),
+            ],
+            [self.vault_token_a.key(), self.vault_token_b.key()],
+        );
+
+        let instruction = Instruction {
+            program_id: self.meteora_program.key(),
+            accounts,
+            data: get_claim_fee_ix_data(u64::MAX),
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.pool.to_account_info(),
+                self.lp_mint.to_account_info(),
+                self.lock_escrow.to_account_info(),
+                self.vault.to_account_info(),
+                self.source_tokens.to_account_info(),
+                self.escrow_vault.to_account_info(),
+                self.token_program.to_account_info(),
+                self.a_token_vault.to_account_info(),
+                self.b_token_vault.to_account_info(),
+                self.a_vault.to_account_info(),
+                self.b_vault.to_account_info(),
+                self.a_vault_lp.to_account_info(),
+                self.b_vault_lp.to_account_info(),
+                self.a_vault_lp_mint.to_account_info(),
+                self.b_vault_lp_mint.to_account_info(),
+                self.vault_token_a.to_account_info(),
+                self.vault_token_b.to_account_info(),
+                self.vault_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    fn pay_out(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+}
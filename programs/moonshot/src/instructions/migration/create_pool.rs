@@ -1,22 +1,27 @@
 use crate::constants::{METEORA_PROGRAM_KEY, QUOTE_MINT};
-use crate::state::bonding_curve::locker::{BondingCurveLockerCtx, IntoBondingCurveLockerCtx};
-use crate::state::{bonding_curve::*, meteora::get_pool_create_ix_data};
-use crate::{errors::ContractError, state::global::*};
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    instruction::Instruction,
-    program::{invoke, invoke_signed},
-    system_instruction,
+use crate::events::{CurveStatusEvent, PoolCreatedEvent, EVENT_SCHEMA_VERSION};
+use crate::state::bonding_curve::locker::BondingCurveLockerCtx;
+use crate::state::versioned::realloc_to_current;
+use crate::state::{
+    bonding_curve::*,
+    meteora::{get_lp_mint_address, get_pool_create_ix_data},
+};
+use crate::{
+    errors::ContractError,
+    state::{global::*, launch_config::*},
 };
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as TokenTransfer};
 use std::str::FromStr;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePoolWithConfig<'info> {
     #[account(
-        mut,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
@@ -24,12 +29,14 @@ pub struct InitializePoolWithConfig<'info> {
     #[account(
         mut,
         seeds = [BondingCurve::SEED_PREFIX.as_bytes(), token_b_mint.to_account_info().key.as_ref()],
-        constraint = bonding_curve.complete == false @ ContractError::BondingCurveComplete,
         bump,
     )]
-    bonding_curve: Box<Account<'info, BondingCurve>>,
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = migration_vault.key() == global.fee_receiver @ ContractError::InvalidFeeReceiver,
+    )]
     /// CHECK: Migration vault account where fee is deposited accounts
     pub migration_vault: UncheckedAccount<'info>,
 
@@ -40,8 +47,11 @@ pub struct InitializePoolWithConfig<'info> {
     /// CHECK: Config for fee
     pub config: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// CHECK: lp mint
+    #[account(
+        mut,
+        address = get_lp_mint_address(pool.key) @ ContractError::InvalidPool
+    )]
+    /// CHECK: lp mint of `pool`
     pub lp_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -89,8 +99,11 @@ pub struct InitializePoolWithConfig<'info> {
     /// CHECK: Accounts to bootstrap the pool with initial liquidity
     pub payer_token_b: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// CHECK: Accounts to bootstrap the pool with initial liquidity
+    #[account(
+        mut,
+        address = get_associated_token_address(payer.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Payer LP account, created by the pool CPI
     pub payer_pool_lp: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -101,7 +114,10 @@ pub struct InitializePoolWithConfig<'info> {
     /// CHECK: Protocol fee token b accounts
     pub protocol_token_b_fee: UncheckedAccount<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Migrator, &payer.key()) @ ContractError::MissingRole
+    )]
     /// CHECK: Admin account
     pub payer: Signer<'info>,
 
@@ -111,6 +127,18 @@ pub struct InitializePoolWithConfig<'info> {
     /// CHECK: Bonding curve token account
     #[account(mut)]
     pub bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = global.token_treasury,
+    )]
+    /// Receives excess tokens when a treasury is configured, otherwise they are burned
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        constraint = launch_config.key() == bonding_curve.launch_config @ ContractError::InvalidLaunchConfig,
+    )]
+    /// Launch config the curve was created with, required unless it used the Global settings
+    pub launch_config: Option<Box<Account<'info, LaunchConfig>>>,
     /// CHECK: Additional program accounts
     pub rent: UncheckedAccount<'info>,
     /// CHECK: Metadata program account
@@ -129,221 +157,377 @@ pub struct InitializePoolWithConfig<'info> {
     pub meteora_program: AccountInfo<'info>,
 }
 
-pub fn initialize_pool_with_config(ctx: Context<InitializePoolWithConfig>) -> Result<()> {
-    let quote_mint: Pubkey = Pubkey::from_str(QUOTE_MINT).unwrap();
-
-    require!(
-        ctx.accounts.bonding_curve.mint.key() == ctx.accounts.token_b_mint.key(),
-        ContractError::NotBondingCurveMint
-    );
+pub fn initialize_pool_with_config<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializePoolWithConfig<'info>>,
+) -> Result<()> {
+    let (status_event, pool_event) = ctx
+        .accounts
+        .into_pool_creator(ctx.bumps.bonding_curve)
+        .create_pool(ctx.remaining_accounts)?;
+    emit_cpi!(status_event);
+    emit_cpi!(pool_event);
+    Ok(())
+}
 
-    require!(
-        quote_mint.key() == ctx.accounts.token_a_mint.key(),
-        ContractError::NotSOL
-    );
+/// Accounts the pool creation works on, borrowed from `create_pool` or `migrate`.
+pub struct PoolCreator<'a, 'info> {
+    pub global: &'a Account<'info, Global>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub bonding_curve_bump: u8,
+    pub launch_config: Option<&'a Account<'info, LaunchConfig>>,
+    pub token_b_mint: &'a Account<'info, Mint>,
+    pub bonding_curve_token_account: &'a Account<'info, TokenAccount>,
+    pub treasury_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub migration_vault: AccountInfo<'info>,
+    pub pool: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+    pub a_vault_lp: AccountInfo<'info>,
+    pub b_vault_lp: AccountInfo<'info>,
+    pub token_a_mint: AccountInfo<'info>,
+    pub a_vault: AccountInfo<'info>,
+    pub b_vault: AccountInfo<'info>,
+    pub a_token_vault: AccountInfo<'info>,
+    pub b_token_vault: AccountInfo<'info>,
+    pub a_vault_lp_mint: AccountInfo<'info>,
+    pub b_vault_lp_mint: AccountInfo<'info>,
+    pub payer_token_a: AccountInfo<'info>,
+    pub payer_token_b: AccountInfo<'info>,
+    pub payer_pool_lp: AccountInfo<'info>,
+    pub protocol_token_a_fee: AccountInfo<'info>,
+    pub protocol_token_b_fee: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub mint_metadata: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub metadata_program: AccountInfo<'info>,
+    pub vault_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
 
-    require!(
-        ctx.accounts.global.meteora_config.key() == ctx.accounts.config.key(),
-        ContractError::InvalidConfig
-    );
+impl<'info> PoolCreator<'_, 'info> {
+    /// Seeds the Meteora pool from the curve reserves and marks the curve `Migrated`.
+    pub fn create_pool(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(CurveStatusEvent, PoolCreatedEvent)> {
+        let quote_mint: Pubkey = Pubkey::from_str(QUOTE_MINT).unwrap();
+
+        self.global.status.require(ProgramAction::Migrate)?;
+        self.bonding_curve.require_not_halted()?;
+        self.bonding_curve.status.require(CurveAction::CreatePool)?;
+
+        require!(
+            self.bonding_curve.mint.key() == self.token_b_mint.key(),
+            ContractError::NotBondingCurveMint
+        );
+
+        require!(
+            quote_mint.key() == self.token_a_mint.key(),
+            ContractError::NotSOL
+        );
+
+        let migration = self.migration_config()?;
+        require!(
+            migration.meteora_config == self.config.key(),
+            ContractError::InvalidConfig
+        );
+
+        // curves created before the version field are grown before their final write
+        realloc_to_current::<BondingCurve>(
+            &self.bonding_curve.to_account_info(),
+            &self.payer,
+            &self.system_program,
+        )?;
+
+        let meteora_program_id: Pubkey = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+
+        let mint_k = self.token_b_mint.key();
+        let mint_authority_signer = BondingCurve::get_signer(&self.bonding_curve_bump, &mint_k);
+        let mint_auth_signer_seeds = &[&mint_authority_signer[..]];
+
+        let bonding_curve_total_lamports = self.bonding_curve.get_lamports();
+        let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
+
+        let available_token_a = bonding_curve_total_lamports
+            .checked_sub(min_balance)
+            .ok_or(ContractError::ArithmeticError)?
+            .checked_sub(migration.migrate_fee_amount)
+            .ok_or(ContractError::ArithmeticError)?
+            .checked_sub(self.global.migration_reserve_lamports)
+            .ok_or(ContractError::ArithmeticError)?;
+
+        let available_token_b = self
+            .bonding_curve
+            .token_total_supply
+            .checked_sub(self.bonding_curve.launch_real_token_reserves(self.global))
+            .ok_or(ContractError::ArithmeticError)?;
+
+        // open the pool at the curve's final price
+        let PoolSeed {
+            token_a_amount,
+            token_b_amount,
+            excess_token_b,
+        } = self.bonding_curve.get_pool_seed(
+            available_token_a,
+            available_token_b,
+            self.global.max_price_deviation_bps,
+        )?;
+
+        // Transfer tokens to user
+
+        let locker = self.bonding_curve_locker_ctx();
+        locker.unlock_ata()?;
+
+        let cpi_accounts = TokenTransfer {
+            from: self.bonding_curve_token_account.to_account_info(),
+            to: self.payer_token_b.clone(),
+            authority: self.bonding_curve.to_account_info(),
+        };
+
+        let signer = BondingCurve::get_signer(&self.bonding_curve_bump, &mint_k);
+        let signer_seeds = &[&signer[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            token_b_amount,
+        )?;
+        if excess_token_b > 0 {
+            self.handle_excess_tokens(excess_token_b, signer_seeds)?;
+        }
+        locker.lock_ata()?;
+
+        // create wrapsol from the curve reserves so the payer never fronts liquidity
+        self.bonding_curve.sub_lamports(token_a_amount)?;
+        self.payer_token_a.add_lamports(token_a_amount)?;
+
+        let cpi_accounts = token::SyncNative {
+            account: self.payer_token_a.clone(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::sync_native(cpi_ctx)?;
+
+        msg!("started meteora");
+
+        let mut accounts = vec![
+            AccountMeta::new(self.pool.key(), false),
+            AccountMeta::new_readonly(self.config.key(), false),
+            AccountMeta::new(self.lp_mint.key(), false),
+            AccountMeta::new_readonly(self.token_a_mint.key(), false),
+            AccountMeta::new_readonly(self.token_b_mint.key(), false),
+            AccountMeta::new(self.a_vault.key(), false),
+            AccountMeta::new(self.b_vault.key(), false),
+            AccountMeta::new(self.a_token_vault.key(), false),
+            AccountMeta::new(self.b_token_vault.key(), false),
+            AccountMeta::new(self.a_vault_lp_mint.key(), false),
+            AccountMeta::new(self.b_vault_lp_mint.key(), false),
+            AccountMeta::new(self.a_vault_lp.key(), false),
+            AccountMeta::new(self.b_vault_lp.key(), false),
+            AccountMeta::new(self.payer_token_a.key(), false),
+            AccountMeta::new(self.payer_token_b.key(), false),
+            AccountMeta::new(self.payer_pool_lp.key(), false),
+            AccountMeta::new(self.protocol_token_a_fee.key(), false),
+            AccountMeta::new(self.protocol_token_b_fee.key(), false),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(self.rent.key(), false),
+            AccountMeta::new(self.mint_metadata.key(), false),
+            AccountMeta::new_readonly(self.metadata_program.key(), false),
+            AccountMeta::new_readonly(self.vault_program.key(), false),
+            AccountMeta::new_readonly(self.token_program.key(), false),
+            AccountMeta::new_readonly(self.associated_token_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+
+        accounts.extend(remaining_accounts.iter().map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: false,
+            is_writable: true,
+        }));
+
+        let data = get_pool_create_ix_data(token_a_amount, token_b_amount);
+
+        let instruction = Instruction {
+            program_id: meteora_program_id,
+            accounts,
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.pool.clone(),
+                self.config.clone(),
+                self.lp_mint.clone(),
+                self.token_a_mint.clone(),
+                self.token_b_mint.to_account_info(),
+                self.a_vault.clone(),
+                self.b_vault.clone(),
+                self.a_token_vault.clone(),
+                self.b_token_vault.clone(),
+                self.a_vault_lp_mint.clone(),
+                self.b_vault_lp_mint.clone(),
+                self.a_vault_lp.clone(),
+                self.b_vault_lp.clone(),
+                self.payer_token_a.clone(),
+                self.payer_token_b.clone(),
+                self.payer_pool_lp.clone(),
+                self.protocol_token_a_fee.clone(),
+                self.protocol_token_b_fee.clone(),
+                self.payer.clone(),
+                self.rent.clone(),
+                self.mint_metadata.clone(),
+                self.metadata_program.clone(),
+                self.vault_program.clone(),
+                self.token_program.to_account_info(),
+                self.associated_token_program.clone(),
+                self.system_program.clone(),
+            ],
+            mint_auth_signer_seeds,
+        )?;
+        msg!("finished meteora");
+        self.bonding_curve.pool = self.pool.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        let status_event = self
+            .bonding_curve
+            .transition(CurveStatus::Migrated, timestamp)?;
+
+        let keeper_reward = self.pay_launch_fee(&migration)?;
+        Ok((
+            status_event,
+            PoolCreatedEvent {
+                schema_version: EVENT_SCHEMA_VERSION,
+                mint: mint_k,
+                pool: self.pool.key(),
+                lp_mint: self.lp_mint.key(),
+                token_a_amount,
+                token_b_amount,
+                excess_token_b,
+                migrate_fee: migration.migrate_fee_amount,
+                keeper_reward,
+                timestamp,
+            },
+        ))
+    }
 
-    require!(
-        ctx.accounts.payer.key() == ctx.accounts.global.global_authority.key(),
-        ContractError::InvalidMigrationAuthority
-    );
+    /// Returns the keeper reward paid to the migrator.
+    pub fn pay_launch_fee(&self, migration: &MigrationConfig) -> Result<u64> {
+        let (fee_amount, keeper_reward) = migration.split_fee(self.global.keeper_reward_amount);
 
-    let meteora_program_id: Pubkey = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+        self.bonding_curve.sub_lamports(fee_amount)?;
+        self.migration_vault.add_lamports(fee_amount)?;
 
-    let mint_k = ctx.accounts.token_b_mint.key();
-    let mint_authority_signer = BondingCurve::get_signer(&ctx.bumps.bonding_curve, &mint_k);
-    let mint_auth_signer_seeds = &[&mint_authority_signer[..]];
+        self.bonding_curve.sub_lamports(keeper_reward)?;
+        self.payer.add_lamports(keeper_reward)?;
+        msg!("keeper reward: {} lamports", keeper_reward);
+        Ok(keeper_reward)
+    }
 
-    let bonding_curve_total_lamports = ctx.accounts.bonding_curve.get_lamports();
-    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE as usize);
+    /// Migration settings of the curve's launch config, or the Global ones.
+    pub fn migration_config(&self) -> Result<MigrationConfig> {
+        if self.bonding_curve.launch_config == Pubkey::default() {
+            return Ok(self.global.migration_config());
+        }
+        let launch_config = self
+            .launch_config
+            .ok_or(ContractError::InvalidLaunchConfig)?;
+        Ok(launch_config.migration)
+    }
 
-    let token_a_amount = bonding_curve_total_lamports
-        .checked_sub(min_balance)
-        .ok_or(ContractError::ArithmeticError)?
-        .checked_sub(ctx.accounts.global.migrate_fee_amount)
-        .ok_or(ContractError::ArithmeticError)?
-        .checked_sub(20_000_000)
-        .ok_or(ContractError::ArithmeticError)?;
+    pub fn handle_excess_tokens(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if self.global.token_treasury == Pubkey::default() {
+            msg!("burning {} excess tokens", amount);
+            return token::burn(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Burn {
+                        mint: self.token_b_mint.to_account_info(),
+                        from: self.bonding_curve_token_account.to_account_info(),
+                        authority: self.bonding_curve.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            );
+        }
 
-    // Transfer tokens to user
-    let token_b_amount = ctx
-        .accounts
-        .global
-        .token_total_supply
-        .checked_sub(ctx.accounts.global.initial_real_token_reserves)
-        .ok_or(ContractError::ArithmeticError)?;
+        let treasury = self
+            .treasury_token_account
+            .ok_or(ContractError::InvalidArgument)?;
+        msg!("moving {} excess tokens to treasury", amount);
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenTransfer {
+                    from: self.bonding_curve_token_account.to_account_info(),
+                    to: treasury.to_account_info(),
+                    authority: self.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
 
-    let locker: &mut BondingCurveLockerCtx = &mut ctx
-        .accounts
-        .into_bonding_curve_locker_ctx(ctx.bumps.bonding_curve);
-    locker.unlock_ata()?;
-
-    let cpi_accounts = TokenTransfer {
-        from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-        to: ctx.accounts.payer_token_b.to_account_info(),
-        authority: ctx.accounts.bonding_curve.to_account_info(),
-    };
-
-    let signer = BondingCurve::get_signer(
-        &ctx.bumps.bonding_curve,
-        ctx.accounts.token_b_mint.to_account_info().key,
-    );
-    let signer_seeds = &[&signer[..]];
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        ),
-        token_b_amount,
-    )?;
-    locker.lock_ata()?;
-
-    // create wrapsol
-    let sol_ix = system_instruction::transfer(
-        &ctx.accounts.payer.to_account_info().key,
-        &ctx.accounts.payer_token_a.to_account_info().key,
-        token_a_amount,
-    );
-
-    invoke(
-        &sol_ix,
-        &[
-            ctx.accounts.payer.to_account_info().clone(),
-            ctx.accounts.payer_token_a.to_account_info().clone(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
-
-    let cpi_accounts = token::SyncNative {
-        account: ctx.accounts.payer_token_a.to_account_info(),
-    };
-
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::sync_native(cpi_ctx)?;
-
-    msg!("started meteora");
-
-    let mut accounts = vec![
-        AccountMeta::new(ctx.accounts.pool.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.config.key(), false),
-        AccountMeta::new(ctx.accounts.lp_mint.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.token_a_mint.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.token_b_mint.key(), false),
-        AccountMeta::new(ctx.accounts.a_vault.key(), false),
-        AccountMeta::new(ctx.accounts.b_vault.key(), false),
-        AccountMeta::new(ctx.accounts.a_token_vault.key(), false),
-        AccountMeta::new(ctx.accounts.b_token_vault.key(), false),
-        AccountMeta::new(ctx.accounts.a_vault_lp_mint.key(), false),
-        AccountMeta::new(ctx.accounts.b_vault_lp_mint.key(), false),
-        AccountMeta::new(ctx.accounts.a_vault_lp.key(), false),
-        AccountMeta::new(ctx.accounts.b_vault_lp.key(), false),
-        AccountMeta::new(ctx.accounts.payer_token_a.key(), false),
-        AccountMeta::new(ctx.accounts.payer_token_b.key(), false),
-        AccountMeta::new(ctx.accounts.payer_pool_lp.key(), false),
-        AccountMeta::new(ctx.accounts.protocol_token_a_fee.key(), false),
-        AccountMeta::new(ctx.accounts.protocol_token_b_fee.key(), false),
-        AccountMeta::new(ctx.accounts.payer.key(), true),
-        AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
-        AccountMeta::new(ctx.accounts.mint_metadata.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.metadata_program.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.vault_program.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.associated_token_program.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
-    ];
-
-    accounts.extend(ctx.remaining_accounts.iter().map(|acc| AccountMeta {
-        pubkey: *acc.key,
-        is_signer: false,
-        is_writable: true,
-    }));
-
-    let data = get_pool_create_ix_data(token_a_amount, token_b_amount);
-
-    let instruction = Instruction {
-        program_id: meteora_program_id,
-        accounts,
-        data,
-    };
-
-    invoke_signed(
-        &instruction,
-        &[
-            ctx.accounts.pool.to_account_info(),
-            ctx.accounts.config.to_account_info(),
-            ctx.accounts.lp_mint.to_account_info(),
-            ctx.accounts.token_a_mint.to_account_info(),
-            ctx.accounts.token_b_mint.to_account_info(),
-            ctx.accounts.a_vault.to_account_info(),
-            ctx.accounts.b_vault.to_account_info(),
-            ctx.accounts.a_token_vault.to_account_info(),
-            ctx.accounts.b_token_vault.to_account_info(),
-            ctx.accounts.a_vault_lp_mint.to_account_info(),
-            ctx.accounts.b_vault_lp_mint.to_account_info(),
-            ctx.accounts.a_vault_lp.to_account_info(),
-            ctx.accounts.b_vault_lp.to_account_info(),
-            ctx.accounts.payer_token_a.to_account_info(),
-            ctx.accounts.payer_token_b.to_account_info(),
-            ctx.accounts.payer_pool_lp.to_account_info(),
-            ctx.accounts.protocol_token_a_fee.to_account_info(),
-            ctx.accounts.protocol_token_b_fee.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-            ctx.accounts.mint_metadata.to_account_info(),
-            ctx.accounts.metadata_program.to_account_info(),
-            ctx.accounts.vault_program.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.associated_token_program.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        mint_auth_signer_seeds,
-    )?;
-    let _ = pay_launch_fee(ctx, bonding_curve_total_lamports - min_balance);
-    Ok(())
+    fn bonding_curve_locker_ctx(&self) -> BondingCurveLockerCtx<'info> {
+        BondingCurveLockerCtx {
+            bonding_curve_bump: self.bonding_curve_bump,
+            mint: Box::new(self.token_b_mint.clone()),
+            bonding_curve: Box::new(self.bonding_curve.clone()),
+            bonding_curve_token_account: Box::new(self.bonding_curve_token_account.clone()),
+            token_program: self.token_program.clone(),
+            global: Box::new(self.global.clone()),
+        }
+    }
 }
 
-pub fn pay_launch_fee(
-    ctx: Context<InitializePoolWithConfig>,
-    bonding_curve_total_lamports: u64,
-) -> Result<()> {
-    // transfer SOL to fee recipient
-    // sender is signer, must go through system program
-    let fee_amount = ctx.accounts.global.migrate_fee_amount;
-    msg!("finished meteora");
-
-    ctx.accounts.bonding_curve.sub_lamports(fee_amount).unwrap();
-    ctx.accounts
-        .migration_vault
-        .add_lamports(fee_amount)
-        .unwrap();
-
-    // msg!("transfer to admin ===>>>{}", bonding_curve_total_lamports);
-    // msg!("transfer to admin ===>>>{}", ctx.accounts.bonding_curve.get_lamports());
-
-    // ctx.accounts.bonding_curve.sub_lamports(bonding_curve_total_lamports).unwrap();
-    // ctx.accounts.payer.add_lamports(bonding_curve_total_lamports).unwrap();
-    Ok(())
+pub trait IntoPoolCreator<'info> {
+    #[allow(clippy::wrong_self_convention)]
+    fn into_pool_creator(&mut self, bonding_curve_bump: u8) -> PoolCreator<'_, 'info>;
 }
 
-impl<'info> IntoBondingCurveLockerCtx<'info> for InitializePoolWithConfig<'info> {
-    fn into_bonding_curve_locker_ctx(
-        &self,
-        bonding_curve_bump: u8,
-    ) -> BondingCurveLockerCtx<'info> {
-        BondingCurveLockerCtx {
+impl<'info> IntoPoolCreator<'info> for InitializePoolWithConfig<'info> {
+    fn into_pool_creator(&mut self, bonding_curve_bump: u8) -> PoolCreator<'_, 'info> {
+        PoolCreator {
+            global: &self.global,
+            bonding_curve: &mut self.bonding_curve,
             bonding_curve_bump,
-            mint: self.token_b_mint.clone(),
-            bonding_curve: self.bonding_curve.clone(),
-            bonding_curve_token_account: self.bonding_curve_token_account.clone(),
-            token_program: self.token_program.clone(),
-            global: self.global.clone(),
+            launch_config: self.launch_config.as_deref(),
+            token_b_mint: &self.token_b_mint,
+            bonding_curve_token_account: &self.bonding_curve_token_account,
+            treasury_token_account: self.treasury_token_account.as_deref(),
+            token_program: &self.token_program,
+            migration_vault: self.migration_vault.to_account_info(),
+            pool: self.pool.to_account_info(),
+            config: self.config.to_account_info(),
+            lp_mint: self.lp_mint.to_account_info(),
+            a_vault_lp: self.a_vault_lp.to_account_info(),
+            b_vault_lp: self.b_vault_lp.to_account_info(),
+            token_a_mint: self.token_a_mint.to_account_info(),
+            a_vault: self.a_vault.to_account_info(),
+            b_vault: self.b_vault.to_account_info(),
+            a_token_vault: self.a_token_vault.to_account_info(),
+            b_token_vault: self.b_token_vault.to_account_info(),
+            a_vault_lp_mint: self.a_vault_lp_mint.to_account_info(),
+            b_vault_lp_mint: self.b_vault_lp_mint.to_account_info(),
+            payer_token_a: self.payer_token_a.to_account_info(),
+            payer_token_b: self.payer_token_b.to_account_info(),
+            payer_pool_lp: self.payer_pool_lp.to_account_info(),
+            protocol_token_a_fee: self.protocol_token_a_fee.to_account_info(),
+            protocol_token_b_fee: self.protocol_token_b_fee.to_account_info(),
+            payer: self.payer.to_account_info(),
+            mint_metadata: self.mint_metadata.to_account_info(),
+            rent: self.rent.to_account_info(),
+            metadata_program: self.metadata_program.to_account_info(),
+            vault_program: self.vault_program.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
         }
     }
 }
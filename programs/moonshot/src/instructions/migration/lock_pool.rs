@@ -1,35 +1,58 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
-use anchor_spl::{associated_token, token::TokenAccount};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::{self, Burn, TokenAccount, Transfer},
+};
 use crate::constants::{VAULT_SEED, METEORA_PROGRAM_KEY};
 use crate::errors::ContractError;
-use crate::Global;
+use crate::events::{CurveStatusEvent, LiquidityPolicyEvent, EVENT_SCHEMA_VERSION};
+use crate::state::bonding_curve::{BondingCurve, CurveAction, CurveStatus};
+use crate::state::versioned::realloc_to_current;
+use crate::util::token_balance;
+use crate::state::global::{Global, ProgramAction, Role};
 use std::str::FromStr;
-use crate::state::meteora::{get_function_hash, get_lock_lp_ix_data};
+use crate::state::meteora::{
+    get_create_lock_escrow_accounts, get_function_hash, get_lock_accounts,
+    get_lock_escrow_address, get_lock_lp_ix_data, get_lp_mint_address,
+};
 
+/// Locks the pool LP on behalf of the program.
+///
+/// Account layout after locking:
+/// - `vault` (`[VAULT_SEED]`) owns `lock_escrow` (`["lock_escrow", pool, vault]` under Meteora)
+/// - `escrow_vault` is the `lock_escrow` ATA for `lp_mint` and holds the locked LP
+/// - `vault_pool_lp` is the `vault` ATA for `lp_mint`, LP passes through it from `payer_pool_lp`
+#[event_cpi]
 #[derive(Accounts)]
 pub struct LockPool<'info> {
     #[account(
-        mut,
         seeds = [Global::SEED_PREFIX.as_bytes()],
-        constraint = global.initialized == true @ ContractError::NotInitialized,
+        constraint = global.initialized @ ContractError::NotInitialized,
         bump,
     )]
     global: Box<Account<'info, Global>>,
     
     #[account(
+        mut,
         seeds = [VAULT_SEED], 
         bump
     )]
-    /// CHECK: Vault account
+    /// CHECK: Vault account, owner of the lock escrow
     pub vault: AccountInfo<'info>,
 
-    #[account(mut)]
-    /// CHECK: Pool account (PDA address)
+    #[account(
+        mut,
+        constraint = pool.key() == bonding_curve.pool @ ContractError::InvalidPool
+    )]
+    /// CHECK: Pool account recorded at migration
     pub pool: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// CHECK: lp mint
+    #[account(
+        mut,
+        address = get_lp_mint_address(pool.key) @ ContractError::InvalidPool
+    )]
+    /// CHECK: lp mint of `pool`
     pub lp_mint: UncheckedAccount<'info>,
     
     #[account(mut)]
@@ -42,6 +65,13 @@ pub struct LockPool<'info> {
     
     /// CHECK: Token B mint
     pub token_b_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), token_b_mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
     
     #[account(mut)]
     /// CHECK: Vault accounts for token A
@@ -59,11 +89,25 @@ pub struct LockPool<'info> {
     /// CHECK: Vault LP accounts and mints for token B
     pub b_vault_lp_mint: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// CHECK: Accounts to bootstrap the pool with initial liquidity
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = payer,
+    )]
+    /// Payer LP account the pool was seeded into
     pub payer_pool_lp: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, constraint = payer.key() == global.migration_authority @ ContractError::InvalidMigrationAuthority)]
+    #[account(
+        mut,
+        address = get_associated_token_address(vault.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Vault LP account, created on demand
+    pub vault_pool_lp: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Migrator, &payer.key()) @ ContractError::MissingRole
+    )]
     pub payer: Signer<'info>,
     
     /// CHECK: Token program account
@@ -72,12 +116,18 @@ pub struct LockPool<'info> {
     pub associated_token_program: UncheckedAccount<'info>,
     /// CHECK: System program account
     pub system_program: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK lock escrow
+    #[account(
+        mut,
+        address = get_lock_escrow_address(pool.key, vault.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: lock escrow owned by the vault
     pub lock_escrow: UncheckedAccount<'info>,
     
-    #[account(mut)]
-    /// CHECK: Escrow vault
+    #[account(
+        mut,
+        address = get_associated_token_address(lock_escrow.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Escrow vault, the lock escrow ATA for `lp_mint`
     pub escrow_vault: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -85,98 +135,275 @@ pub struct LockPool<'info> {
     pub meteora_program: AccountInfo<'info>,
     
     /// CHECK: Meteora Event Autority
-    pub event_authority: AccountInfo<'info>
+    pub meteora_event_authority: AccountInfo<'info>,
 }
 
 pub fn lock_pool(
     ctx: Context<LockPool>,
 ) -> Result<()> {
-    let _clientbump = ctx.bumps.vault.to_le_bytes();
-    let signer_seeds: &[&[&[u8]]] = &[
-        &[VAULT_SEED, _clientbump.as_ref()]
-    ];
-    let meteora_program_id: Pubkey = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
-    let source_tokens = ctx.accounts.payer_pool_lp.clone();
-    let lp_mint_amount = ctx.accounts.payer_pool_lp.amount;
-    let escrow_accounts = vec![
-        AccountMeta::new(ctx.accounts.pool.key(), false),
-        AccountMeta::new(ctx.accounts.lock_escrow.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.payer.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.lp_mint.key(), false),
-        AccountMeta::new(ctx.accounts.payer.key(), true),
-        AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
-    ];
-
-    let escrow_instruction = Instruction {
-        program_id: meteora_program_id,
-        accounts: escrow_accounts,
-        data: get_function_hash("global", "create_lock_escrow").into(),
-    };
-
-    invoke_signed(&escrow_instruction, 
-        &[
-            ctx.accounts.pool.to_account_info(),
-            ctx.accounts.lock_escrow.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.lp_mint.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        signer_seeds
-    )?;
-
-    if ctx.accounts.escrow_vault.get_lamports() == 0 {
-        associated_token::create(CpiContext::new(
-            ctx.accounts.associated_token_program.to_account_info(),
-            associated_token::Create {
-                payer: ctx.accounts.payer.to_account_info(),
-                associated_token: ctx.accounts.escrow_vault.to_account_info(),
-                authority: ctx.accounts.lock_escrow.to_account_info(),
-                mint: ctx.accounts.lp_mint.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-            },
-        ))?;
+    let (status_event, policy_event) = ctx
+        .accounts
+        .into_lp_locker(ctx.bumps.vault)
+        .lock_pool()?;
+    emit_cpi!(status_event);
+    emit_cpi!(policy_event);
+    Ok(())
+}
+
+/// Accounts the LP lock works on, borrowed from `lock_pool` or `migrate`.
+pub struct LpLocker<'a, 'info> {
+    pub global: &'a Account<'info, Global>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub vault: AccountInfo<'info>,
+    pub vault_bump: u8,
+    pub pool: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+    pub a_vault_lp: AccountInfo<'info>,
+    pub b_vault_lp: AccountInfo<'info>,
+    pub token_b_mint: AccountInfo<'info>,
+    pub a_vault: AccountInfo<'info>,
+    pub b_vault: AccountInfo<'info>,
+    pub a_vault_lp_mint: AccountInfo<'info>,
+    pub b_vault_lp_mint: AccountInfo<'info>,
+    pub payer_pool_lp: AccountInfo<'info>,
+    pub vault_pool_lp: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub lock_escrow: AccountInfo<'info>,
+    pub escrow_vault: AccountInfo<'info>,
+}
+
+impl LpLocker<'_, '_> {
+    /// Burns and locks the payer's LP per the liquidity policy and marks the curve `LpLocked`.
+    pub fn lock_pool(&mut self) -> Result<(CurveStatusEvent, LiquidityPolicyEvent)> {
+        self.global.status.require(ProgramAction::Migrate)?;
+        self.bonding_curve.require_not_halted()?;
+        self.bonding_curve.status.require(CurveAction::LockPool)?;
+
+        // `migrate` hands over an LP account the pool CPI just created, so check it here
+        require_keys_eq!(
+            *self.payer_pool_lp.owner,
+            token::ID,
+            ContractError::InvalidArgument
+        );
+        let lp_amount = token_balance(&self.payer_pool_lp)?;
+        require!(lp_amount > 0, ContractError::ZeroLpAmount);
+        let policy = self.global.liquidity_policy;
+        let (lock_amount, burn_amount) = policy
+            .split(lp_amount)
+            .ok_or(ContractError::ArithmeticError)?;
+        msg!("LockPool: {:?} lock {} burn {}", policy, lock_amount, burn_amount);
+
+        if burn_amount > 0 {
+            token::burn(
+                CpiContext::new(
+                    self.token_program.clone(),
+                    Burn {
+                        mint: self.lp_mint.clone(),
+                        from: self.payer_pool_lp.clone(),
+                        authority: self.payer.clone(),
+                    },
+                ),
+                burn_amount,
+            )?;
+        }
+        if lock_amount > 0 {
+            self.lock_liquidity(lock_amount)?;
+        }
+
+        // curves migrated by an older build can still carry the v1 layout
+        realloc_to_current::<BondingCurve>(
+            &self.bonding_curve.to_account_info(),
+            &self.payer,
+            &self.system_program,
+        )?;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let status_event = self
+            .bonding_curve
+            .transition(CurveStatus::LpLocked, timestamp)?;
+        Ok((
+            status_event,
+            self.policy_event(lp_amount, lock_amount, burn_amount, timestamp),
+        ))
     }
 
-    let lock_accounts = vec![
-        AccountMeta::new(ctx.accounts.pool.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.lp_mint.key(), false),
-        AccountMeta::new(ctx.accounts.lock_escrow.key(), false),
-        AccountMeta::new(ctx.accounts.payer.key(), true),
-        AccountMeta::new(source_tokens.key(), false),
-        AccountMeta::new(ctx.accounts.escrow_vault.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.a_vault.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.b_vault.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.a_vault_lp.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.b_vault_lp.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.a_vault_lp_mint.key(), false),
-        AccountMeta::new_readonly(ctx.accounts.b_vault_lp_mint.key(), false),
-    ];
-    
-    let lock_instruction = Instruction {
-        program_id: meteora_program_id,
-        accounts: lock_accounts,
-        data: get_lock_lp_ix_data(lp_mint_amount),
-    };
-
-    invoke_signed(&lock_instruction, 
-        &[
-            ctx.accounts.pool.to_account_info(),
-            ctx.accounts.lp_mint.to_account_info(),
-            ctx.accounts.lock_escrow.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            source_tokens.to_account_info(),
-            ctx.accounts.escrow_vault.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.a_vault.to_account_info(),
-            ctx.accounts.b_vault.to_account_info(),
-            ctx.accounts.a_vault_lp.to_account_info(),
-            ctx.accounts.b_vault_lp.to_account_info(),
-            ctx.accounts.a_vault_lp_mint.to_account_info(),
-            ctx.accounts.b_vault_lp_mint.to_account_info(),
-        ],
-        signer_seeds
-    )?;
-    Ok(())
+    /// Reports how the LP was split. No escrow is created when nothing is locked, as under
+    /// `LiquidityPolicy::Burn`, so `lock_escrow` is then the default key.
+    pub fn policy_event(
+        &self,
+        lp_amount: u64,
+        lock_amount: u64,
+        burn_amount: u64,
+        timestamp: i64,
+    ) -> LiquidityPolicyEvent {
+        let lock_escrow = if lock_amount > 0 {
+            self.lock_escrow.key()
+        } else {
+            Pubkey::default()
+        };
+        LiquidityPolicyEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: self.token_b_mint.key(),
+            policy: self.global.liquidity_policy,
+            lp_amount,
+            lock_amount,
+            burn_amount,
+            lock_escrow,
+            timestamp,
+        }
+    }
+
+    fn lock_liquidity(&self, lock_amount: u64) -> Result<()> {
+        let _clientbump = self.vault_bump.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[
+            &[VAULT_SEED, _clientbump.as_ref()]
+        ];
+
+        // hand the LP to the vault so the escrow never depends on the migration signer
+        if self.vault_pool_lp.get_lamports() == 0 {
+            associated_token::create(CpiContext::new(
+                self.associated_token_program.clone(),
+                associated_token::Create {
+                    payer: self.payer.clone(),
+                    associated_token: self.vault_pool_lp.clone(),
+                    authority: self.vault.clone(),
+                    mint: self.lp_mint.clone(),
+                    token_program: self.token_program.clone(),
+                    system_program: self.system_program.clone(),
+                },
+            ))?;
+        }
+        token::transfer(
+            CpiContext::new(
+                self.token_program.clone(),
+                Transfer {
+                    from: self.payer_pool_lp.clone(),
+                    to: self.vault_pool_lp.clone(),
+                    authority: self.payer.clone(),
+                },
+            ),
+            lock_amount,
+        )?;
+
+        invoke_signed(&self.create_lock_escrow_instruction(),
+            &[
+                self.pool.clone(),
+                self.lock_escrow.clone(),
+                self.vault.clone(),
+                self.lp_mint.clone(),
+                self.payer.clone(),
+                self.system_program.clone(),
+            ],
+            signer_seeds
+        )?;
+
+        if self.escrow_vault.get_lamports() == 0 {
+            associated_token::create(CpiContext::new(
+                self.associated_token_program.clone(),
+                associated_token::Create {
+                    payer: self.payer.clone(),
+                    associated_token: self.escrow_vault.clone(),
+                    authority: self.lock_escrow.clone(),
+                    mint: self.lp_mint.clone(),
+                    token_program: self.token_program.clone(),
+                    system_program: self.system_program.clone(),
+                },
+            ))?;
+        }
+
+        invoke_signed(&self.lock_instruction(lock_amount),
+            &[
+                self.pool.clone(),
+                self.lp_mint.clone(),
+                self.lock_escrow.clone(),
+                self.vault.clone(),
+                self.vault_pool_lp.clone(),
+                self.escrow_vault.clone(),
+                self.token_program.clone(),
+                self.a_vault.clone(),
+                self.b_vault.clone(),
+                self.a_vault_lp.clone(),
+                self.b_vault_lp.clone(),
+                self.a_vault_lp_mint.clone(),
+                self.b_vault_lp_mint.clone(),
+            ],
+            signer_seeds
+        )?;
+        Ok(())
+    }
+
+    /// Meteora `create_lock_escrow` for the vault, paid by the migration signer.
+    pub fn create_lock_escrow_instruction(&self) -> Instruction {
+        Instruction {
+            program_id: Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap(),
+            accounts: get_create_lock_escrow_accounts(
+                self.pool.key(),
+                self.lock_escrow.key(),
+                self.vault.key(),
+                self.lp_mint.key(),
+                self.payer.key(),
+                self.system_program.key(),
+            ),
+            data: get_function_hash("global", "create_lock_escrow").into(),
+        }
+    }
+
+    /// Meteora `lock` moving `lock_amount` LP from the vault ATA into the escrow, signed by the vault.
+    pub fn lock_instruction(&self, lock_amount: u64) -> Instruction {
+        Instruction {
+            program_id: Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap(),
+            accounts: get_lock_accounts(
+                self.pool.key(),
+                self.lp_mint.key(),
+                self.lock_escrow.key(),
+                self.vault.key(),
+                self.vault_pool_lp.key(),
+                self.token_program.key(),
+                [
+                    self.a_vault.key(),
+                    self.b_vault.key(),
+                    self.a_vault_lp.key(),
+                    self.b_vault_lp.key(),
+                    self.a_vault_lp_mint.key(),
+                    self.b_vault_lp_mint.key(),
+                ],
+            ),
+            data: get_lock_lp_ix_data(lock_amount),
+        }
+    }
+}
+
+pub trait IntoLpLocker<'info> {
+    #[allow(clippy::wrong_self_convention)]
+    fn into_lp_locker(&mut self, vault_bump: u8) -> LpLocker<'_, 'info>;
+}
+
+impl<'info> IntoLpLocker<'info> for LockPool<'info> {
+    fn into_lp_locker(&mut self, vault_bump: u8) -> LpLocker<'_, 'info> {
+        LpLocker {
+            global: &self.global,
+            bonding_curve: &mut self.bonding_curve,
+            vault: self.vault.clone(),
+            vault_bump,
+            pool: self.pool.to_account_info(),
+            lp_mint: self.lp_mint.to_account_info(),
+            a_vault_lp: self.a_vault_lp.to_account_info(),
+            b_vault_lp: self.b_vault_lp.to_account_info(),
+            token_b_mint: self.token_b_mint.to_account_info(),
+            a_vault: self.a_vault.to_account_info(),
+            b_vault: self.b_vault.to_account_info(),
+            a_vault_lp_mint: self.a_vault_lp_mint.to_account_info(),
+            b_vault_lp_mint: self.b_vault_lp_mint.to_account_info(),
+            payer_pool_lp: self.payer_pool_lp.to_account_info(),
+            vault_pool_lp: self.vault_pool_lp.to_account_info(),
+            payer: self.payer.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            lock_escrow: self.lock_escrow.to_account_info(),
+            escrow_vault: self.escrow_vault.to_account_info(),
+        }
+    }
 }
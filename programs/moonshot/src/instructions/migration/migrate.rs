@@ -0,0 +1,274 @@
+use crate::constants::{QUOTE_MINT, VAULT_SEED};
+use crate::errors::ContractError;
+use crate::events::{MigrationEvent, EVENT_SCHEMA_VERSION};
+use crate::instructions::migration::{create_pool::PoolCreator, lock_pool::LpLocker};
+use crate::state::meteora::{get_lock_escrow_address, get_lp_mint_address};
+use crate::state::{bonding_curve::*, global::*, launch_config::*};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use std::str::FromStr;
+
+/// `create_pool` and `lock_pool` in one instruction, the LP never leaves the transaction unlocked.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), token_b_mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    /// CHECK: Vault account, owner of the lock escrow
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = migration_vault.key() == global.fee_receiver @ ContractError::InvalidFeeReceiver,
+    )]
+    /// CHECK: Migration vault account where fee is deposited accounts
+    pub migration_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pool account (PDA address)
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Config for fee
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_lp_mint_address(pool.key) @ ContractError::InvalidPool
+    )]
+    /// CHECK: lp mint of `pool`
+    pub lp_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token A LP
+    pub a_vault_lp: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token A LP
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    #[account(address = Pubkey::from_str(QUOTE_MINT).unwrap() @ ContractError::NotSOL)]
+    /// CHECK: Token A mint
+    pub token_a_mint: UncheckedAccount<'info>,
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Vault accounts for token A
+    pub a_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Vault accounts for token B
+    pub b_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Vault LP accounts and mints
+    pub a_token_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Vault LP accounts and mints for token B
+    pub b_token_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Vault LP accounts and mints for token A
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Vault LP accounts and mints for token B
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Accounts to bootstrap the pool with initial liquidity
+    pub payer_token_a: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Accounts to bootstrap the pool with initial liquidity
+    pub payer_token_b: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(payer.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Payer LP account, created by the pool CPI and emptied by the lock in the same instruction
+    pub payer_pool_lp: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(vault.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Vault LP account, created on demand
+    pub vault_pool_lp: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Protocol fee token a accounts
+    pub protocol_token_a_fee: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Protocol fee token b accounts
+    pub protocol_token_b_fee: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = global.can_migrate(&payer.key()) @ ContractError::MissingRole
+    )]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: LP mint metadata PDA. Metaplex do the checking.
+    pub mint_metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = global.token_treasury,
+    )]
+    /// Receives excess tokens when a treasury is configured, otherwise they are burned
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        constraint = launch_config.key() == bonding_curve.launch_config @ ContractError::InvalidLaunchConfig,
+    )]
+    /// Launch config the curve was created with, required unless it used the Global settings
+    pub launch_config: Option<Box<Account<'info, LaunchConfig>>>,
+
+    #[account(
+        mut,
+        address = get_lock_escrow_address(pool.key, vault.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: lock escrow owned by the vault
+    pub lock_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(lock_escrow.key, lp_mint.key) @ ContractError::InvalidArgument
+    )]
+    /// CHECK: Escrow vault, the lock escrow ATA for `lp_mint`
+    pub escrow_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Additional program accounts
+    pub rent: UncheckedAccount<'info>,
+    /// CHECK: Metadata program account
+    pub metadata_program: UncheckedAccount<'info>,
+    /// CHECK: Vault program account
+    pub vault_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Associated token program account
+    pub associated_token_program: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+
+    #[account(mut)]
+    /// CHECK: Meteora Program
+    pub meteora_program: AccountInfo<'info>,
+
+    /// CHECK: Meteora Event Autority
+    pub meteora_event_authority: AccountInfo<'info>,
+}
+
+impl<'info> Migrate<'info> {
+    pub fn handler(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+        let (migrated_event, pool_event) = ctx
+            .accounts
+            .pool_creator(ctx.bumps.bonding_curve)
+            .create_pool(ctx.remaining_accounts)?;
+        msg!("Migrate: pool created");
+
+        let (locked_event, policy_event) = ctx.accounts.lp_locker(ctx.bumps.vault).lock_pool()?;
+        msg!("Migrate: {} lp locked", policy_event.lp_amount);
+
+        let migration_event = MigrationEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: ctx.accounts.token_b_mint.key(),
+            pool: ctx.accounts.pool.key(),
+            lp_mint: ctx.accounts.lp_mint.key(),
+            lp_amount: policy_event.lp_amount,
+            lock_escrow: policy_event.lock_escrow,
+            timestamp: policy_event.timestamp,
+        };
+        emit_cpi!(migrated_event);
+        emit_cpi!(pool_event);
+        emit_cpi!(locked_event);
+        emit_cpi!(policy_event);
+        emit_cpi!(migration_event);
+        Ok(())
+    }
+
+    fn pool_creator(&mut self, bonding_curve_bump: u8) -> PoolCreator<'_, 'info> {
+        PoolCreator {
+            global: &self.global,
+            bonding_curve: &mut self.bonding_curve,
+            bonding_curve_bump,
+            launch_config: self.launch_config.as_deref(),
+            token_b_mint: &self.token_b_mint,
+            bonding_curve_token_account: &self.bonding_curve_token_account,
+            treasury_token_account: self.treasury_token_account.as_deref(),
+            token_program: &self.token_program,
+            migration_vault: self.migration_vault.to_account_info(),
+            pool: self.pool.to_account_info(),
+            config: self.config.to_account_info(),
+            lp_mint: self.lp_mint.to_account_info(),
+            a_vault_lp: self.a_vault_lp.to_account_info(),
+            b_vault_lp: self.b_vault_lp.to_account_info(),
+            token_a_mint: self.token_a_mint.to_account_info(),
+            a_vault: self.a_vault.to_account_info(),
+            b_vault: self.b_vault.to_account_info(),
+            a_token_vault: self.a_token_vault.to_account_info(),
+            b_token_vault: self.b_token_vault.to_account_info(),
+            a_vault_lp_mint: self.a_vault_lp_mint.to_account_info(),
+            b_vault_lp_mint: self.b_vault_lp_mint.to_account_info(),
+            payer_token_a: self.payer_token_a.to_account_info(),
+            payer_token_b: self.payer_token_b.to_account_info(),
+            payer_pool_lp: self.payer_pool_lp.to_account_info(),
+            protocol_token_a_fee: self.protocol_token_a_fee.to_account_info(),
+            protocol_token_b_fee: self.protocol_token_b_fee.to_account_info(),
+            payer: self.payer.to_account_info(),
+            mint_metadata: self.mint_metadata.to_account_info(),
+            rent: self.rent.to_account_info(),
+            metadata_program: self.metadata_program.to_account_info(),
+            vault_program: self.vault_program.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+
+    fn lp_locker(&mut self, vault_bump: u8) -> LpLocker<'_, 'info> {
+        LpLocker {
+            global: &self.global,
+            bonding_curve: &mut self.bonding_curve,
+            vault: self.vault.clone(),
+            vault_bump,
+            pool: self.pool.to_account_info(),
+            lp_mint: self.lp_mint.to_account_info(),
+            a_vault_lp: self.a_vault_lp.to_account_info(),
+            b_vault_lp: self.b_vault_lp.to_account_info(),
+            token_b_mint: self.token_b_mint.to_account_info(),
+            a_vault: self.a_vault.to_account_info(),
+            b_vault: self.b_vault.to_account_info(),
+            a_vault_lp_mint: self.a_vault_lp_mint.to_account_info(),
+            b_vault_lp_mint: self.b_vault_lp_mint.to_account_info(),
+            payer_pool_lp: self.payer_pool_lp.to_account_info(),
+            vault_pool_lp: self.vault_pool_lp.to_account_info(),
+            payer: self.payer.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            lock_escrow: self.lock_escrow.to_account_info(),
+            escrow_vault: self.escrow_vault.to_account_info(),
+        }
+    }
+}
@@ -1,4 +1,10 @@
 pub mod create_pool;
 pub use create_pool::*;
 pub mod lock_pool;
-pub use lock_pool::*;
\ No newline at end of file
+pub use lock_pool::*;
+pub mod migrate;
+pub use migrate::*;
+pub mod sweep_curve_remainder;
+pub use sweep_curve_remainder::*;
+pub mod claim_lp_fees;
+pub use claim_lp_fees::*;
@@ -0,0 +1,86 @@
+use crate::{
+    errors::ContractError,
+    events::{SweepEvent, EVENT_SCHEMA_VERSION},
+    state::{bonding_curve::*, global::*, versioned::realloc_to_current},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepCurveRemainder<'info> {
+    #[account(
+        mut,
+        constraint = global.has_role(Role::Treasurer, &treasurer.key()) @ ContractError::MissingRole
+    )]
+    treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX.as_bytes()],
+        constraint = global.initialized @ ContractError::NotInitialized,
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    /// CHECK: only used to derive the bonding curve
+    mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = remainder_receiver.key() == global.remainder_receiver @ ContractError::InvalidFeeReceiver,
+    )]
+    /// CHECK: asserted against the global remainder receiver
+    remainder_receiver: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+impl SweepCurveRemainder<'_> {
+    pub fn handler(ctx: Context<SweepCurveRemainder>) -> Result<()> {
+        realloc_to_current::<BondingCurve>(
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.treasurer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        ctx.accounts
+            .global
+            .status
+            .require(ProgramAction::Withdraw)?;
+        ctx.accounts
+            .bonding_curve
+            .status
+            .require(CurveAction::Sweep)?;
+        require_keys_neq!(
+            ctx.accounts.global.remainder_receiver,
+            Pubkey::default(),
+            ContractError::InvalidFeeReceiver
+        );
+
+        // everything above rent exemption is residue once the pool holds the liquidity
+        let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
+        let amount = ctx
+            .accounts
+            .bonding_curve
+            .get_lamports()
+            .saturating_sub(min_balance);
+
+        ctx.accounts.bonding_curve.sub_lamports(amount)?;
+        ctx.accounts.remainder_receiver.add_lamports(amount)?;
+        msg!("SweepCurveRemainder: {} lamports", amount);
+
+        emit_cpi!(SweepEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: ctx.accounts.mint.key(),
+            receiver: ctx.accounts.remainder_receiver.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -3,14 +3,19 @@ pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod merkle;
 pub mod state;
 pub mod util;
 use instructions::{
-    add_wl::*, create_bonding_curve::*, create_pool::*, initialize::*, lock_pool::*, remove_wl::*,
-    set_params::*, swap::*,
+    accept_authority::*, add_wl::*, add_wl_batch::*, allow_wallet::*, approve_proposal::*, cancel_authority::*, cancel_params::*, claim_lp_fees::*, close_curve::*, create_bonding_curve::*, create_pool::*, create_proposal::*, deny_wallet::*,
+    execute_params::*, execute_proposal::*, grant_role::*, init_council::*, init_launch_config::*, initialize::*, lock_pool::*, migrate::*, migrate_curve::*, migrate_global::*,
+    pause::*, propose_authority::*, refund_curve::*, remove_wl::*, remove_wl_batch::*, revoke_role::*, set_curve_flags::*, set_deny_list::*, set_fees::*, set_params::*, set_wl_root::*, swap::*, sweep_curve_remainder::*, update_launch_config::*, update_wl::*,
 };
 use state::bonding_curve::CreateBondingCurveParams;
+use state::council::AdminAction;
 use state::global::*;
+use state::launch_config::{LaunchConfigInput, LaunchConfigParams};
+use state::whitelist::WhitelistInput;
 
 declare_id!("GbguYRqMUzErdhvxLL2dNGqi8wLzWnkp87wd7MnCqkZ3");
 
@@ -27,7 +32,84 @@ pub mod moonshot {
         SetParams::handler(ctx, params)
     }
 
-    pub fn create_pool(ctx: Context<InitializePoolWithConfig>) -> Result<()> {
+    pub fn set_fees(ctx: Context<SetFees>, fees: FeeSettingsInput) -> Result<()> {
+        SetFees::handler(ctx, fees)
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        Pause::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        GrantRole::handler(ctx, role, holder)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        RevokeRole::handler(ctx, role)
+    }
+
+    pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+        MigrateGlobal::handler(ctx)
+    }
+
+    pub fn init_launch_config(
+        ctx: Context<InitLaunchConfig>,
+        index: u16,
+        params: LaunchConfigParams,
+    ) -> Result<()> {
+        InitLaunchConfig::handler(ctx, index, params)
+    }
+
+    pub fn update_launch_config(
+        ctx: Context<UpdateLaunchConfig>,
+        input: LaunchConfigInput,
+    ) -> Result<()> {
+        UpdateLaunchConfig::handler(ctx, input)
+    }
+
+    pub fn execute_params(ctx: Context<ExecuteParams>) -> Result<()> {
+        ExecuteParams::handler(ctx)
+    }
+
+    pub fn cancel_params(ctx: Context<CancelParams>) -> Result<()> {
+        CancelParams::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        ProposeAuthority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        AcceptAuthority::handler(ctx)
+    }
+
+    pub fn cancel_authority(ctx: Context<CancelAuthority>) -> Result<()> {
+        CancelAuthority::handler(ctx)
+    }
+
+    pub fn init_council(
+        ctx: Context<InitCouncil>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        InitCouncil::handler(ctx, signers, threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        CreateProposal::handler(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        ApproveProposal::handler(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        ExecuteProposal::handler(ctx)
+    }
+
+    pub fn create_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializePoolWithConfig<'info>>,
+    ) -> Result<()> {
         instructions::initialize_pool_with_config(ctx)
     }
 
@@ -35,12 +117,58 @@ pub mod moonshot {
         instructions::lock_pool(ctx)
     }
 
+    pub fn migrate<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+        Migrate::handler(ctx)
+    }
+
+    pub fn sweep_curve_remainder(ctx: Context<SweepCurveRemainder>) -> Result<()> {
+        SweepCurveRemainder::handler(ctx)
+    }
+
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        ClaimLpFees::handler(ctx)
+    }
+
     pub fn add_wl(ctx: Context<AddWl>, new_creator: Pubkey) -> Result<()> {
         AddWl::handler(ctx, new_creator)
     }
 
-    pub fn remove_wl(_ctx: Context<RemoveWl>) -> Result<()> {
-        Ok(())
+    pub fn remove_wl(ctx: Context<RemoveWl>) -> Result<()> {
+        RemoveWl::handler(ctx)
+    }
+
+    pub fn add_wl_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddWlBatch<'info>>,
+        creators: Vec<Pubkey>,
+    ) -> Result<()> {
+        AddWlBatch::handler(ctx, creators)
+    }
+
+    pub fn remove_wl_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveWlBatch<'info>>,
+        creators: Vec<Pubkey>,
+    ) -> Result<()> {
+        RemoveWlBatch::handler(ctx, creators)
+    }
+
+    pub fn deny_wallet(ctx: Context<DenyWallet>, wallet: Pubkey, reason: u16) -> Result<()> {
+        DenyWallet::handler(ctx, wallet, reason)
+    }
+
+    pub fn allow_wallet(ctx: Context<AllowWallet>) -> Result<()> {
+        AllowWallet::handler(ctx)
+    }
+
+    pub fn set_deny_list(ctx: Context<SetDenyList>, enabled: bool) -> Result<()> {
+        SetDenyList::handler(ctx, enabled)
+    }
+
+    pub fn update_wl(ctx: Context<UpdateWl>, input: WhitelistInput) -> Result<()> {
+        UpdateWl::handler(ctx, input)
+    }
+
+    pub fn set_wl_root(ctx: Context<SetWlRoot>, root: [u8; 32]) -> Result<()> {
+        SetWlRoot::handler(ctx, root)
     }
 
     #[access_control(ctx.accounts.validate(&params))]
@@ -51,8 +179,27 @@ pub mod moonshot {
         CreateBondingCurve::handler(ctx, params)
     }
 
+    pub fn set_curve_flags(ctx: Context<SetCurveFlags>, params: CurveFlagsParams) -> Result<()> {
+        SetCurveFlags::handler(ctx, params)
+    }
+
+    pub fn migrate_curve(ctx: Context<MigrateCurve>) -> Result<()> {
+        MigrateCurve::handler(ctx)
+    }
+
+    pub fn refund_curve(ctx: Context<RefundCurve>) -> Result<()> {
+        RefundCurve::handler(ctx)
+    }
+
+    pub fn close_curve(ctx: Context<CloseCurve>) -> Result<()> {
+        CloseCurve::handler(ctx)
+    }
+
     #[access_control(ctx.accounts.validate(&params))]
-    pub fn swap(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        params: SwapParams,
+    ) -> Result<()> {
         Swap::handler(ctx, params)
     }
 }
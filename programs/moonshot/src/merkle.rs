@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+/// Longest accepted proof, enough for 2^24 leaves.
+pub const MAX_PROOF_LEN: usize = 24;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of a key list, domain separated from inner nodes.
+pub fn leaf(key: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, key.as_ref()]).to_bytes()
+}
+
+/// Inner node over a sorted pair, so proofs carry no left/right flags.
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.len() <= MAX_PROOF_LEN
+        && proof
+            .iter()
+            .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+            == *root
+}
+
+/// Off-chain helper, root over `keys` in the given order. An unpaired node moves
+/// up a level unchanged.
+pub fn root(keys: &[Pubkey]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = keys.iter().map(leaf).collect();
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Off-chain helper, proof for `keys[index]` against `root(keys)`.
+pub fn proof(keys: &[Pubkey], mut index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= keys.len() {
+        return None;
+    }
+    let mut level: Vec<[u8; 32]> = keys.iter().map(leaf).collect();
+    let mut proof = vec![];
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(proof)
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => hash_pair(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_key_proves() {
+        for count in [1, 2, 3, 7, 8, 33] {
+            let keys: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+            let root = root(&keys);
+            for (i, key) in keys.iter().enumerate() {
+                let proof = proof(&keys, i).unwrap();
+                assert!(verify(&proof, &root, leaf(key)), "{} of {}", i, count);
+            }
+            assert!(proof(&keys, count).is_none());
+        }
+    }
+
+    #[test]
+    fn test_rejects_outsiders_and_bad_proofs() {
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let root = root(&keys);
+        let proof = proof(&keys, 2).unwrap();
+
+        assert!(!verify(&proof, &root, leaf(&Pubkey::new_unique())));
+        assert!(!verify(&proof, &root, leaf(&keys[1])));
+        assert!(!verify(&proof[1..], &root, leaf(&keys[2])));
+        assert!(!verify(&[], &root, leaf(&keys[2])));
+
+        let too_long = vec![[0; 32]; MAX_PROOF_LEN + 1];
+        assert!(!verify(&too_long, &root, leaf(&keys[0])));
+    }
+
+    #[test]
+    fn test_single_key_root_is_its_leaf() {
+        let key = Pubkey::new_unique();
+        assert_eq!(root(&[key]), leaf(&key));
+        assert!(verify(&[], &root(&[key]), leaf(&key)));
+        assert_eq!(root(&[]), [0; 32]);
+    }
+}
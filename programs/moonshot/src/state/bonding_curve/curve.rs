@@ -1,7 +1,10 @@
 use crate::errors::ContractError;
+use crate::events::{CurveStatusEvent, FeeShare, TradeEvent, TradeVenue, EVENT_SCHEMA_VERSION};
 use crate::state::bonding_curve::locker::BondingCurveLockerCtx;
 use crate::state::bonding_curve::*;
-use crate::util::bps_mul;
+use crate::state::launch_config::{FeeSchedule, LaunchTerms};
+use crate::state::versioned::CURRENT_VERSION;
+use crate::util::{bps_mul, BASIS_POINTS_DIVISOR};
 use crate::Global;
 use anchor_lang::prelude::*;
 use std::fmt::{self};
@@ -20,30 +23,28 @@ impl BondingCurve {
         let slots_passed = time_diff / 400;
         msg!("Time diff: {} ms ({} slots)", time_diff, slots_passed);
 
-        let mut sol_fee: u64 = 0;
+        let fee_bps = self.fees().fee_bps_at(slots_passed)?;
+        msg!("Fee Bps: {}", fee_bps);
 
-        if slots_passed < 150 {
-            msg!("Phase 1: 99% fees between slot 0 - 150");
-            sol_fee = bps_mul(9900, amount, 10_000).unwrap();
-        } else if slots_passed >= 150 && slots_passed <= 250 {
-            msg!("Phase 2: Linear decrease between 150 - 250");
+        let sol_fee =
+            bps_mul(fee_bps, amount, BASIS_POINTS_DIVISOR).ok_or(ContractError::ArithmeticError)?;
+        Ok(sol_fee)
+    }
 
-            // Calculate the minimum fee bps (at slot 250) scaled by 10000 for precision
-            let fee_bps = (-8_300_000_i64)
-                .checked_mul(slots_passed)
-                .ok_or(ContractError::ArithmeticError)?
-                .checked_add(2_162_600_000)
-                .ok_or(ContractError::ArithmeticError)?
-                .checked_div(1_000_000)
-                .ok_or(ContractError::ArithmeticError)?;
-            msg!("Fee Bps: {}", fee_bps);
+    /// Fee schedule the curve charges, v2 curves that predate the field read it zeroed.
+    pub fn fees(&self) -> FeeSchedule {
+        if self.fee_schedule == FeeSchedule::UNSET {
+            return FeeSchedule::default();
+        }
+        self.fee_schedule
+    }
 
-            sol_fee = bps_mul(fee_bps as u64, amount, 10_000).unwrap();
-        } else if slots_passed > 250 {
-            msg!("Phase 3: 1% fees after 250");
-            sol_fee = bps_mul(100, amount, 10_000).unwrap();
+    /// Real token reserves the curve launched with, v1 curves did not record them.
+    pub fn launch_real_token_reserves(&self, global: &Global) -> u64 {
+        if self.initial_real_token_reserves == 0 {
+            return global.initial_real_token_reserves;
         }
-        Ok(sol_fee)
+        self.initial_real_token_reserves
     }
 
     pub fn get_signer<'a>(bump: &'a u8, mint: &'a Pubkey) -> [&'a [u8]; 3] {
@@ -58,7 +59,7 @@ impl BondingCurve {
         &mut self,
         mint: Pubkey,
         creator: Pubkey,
-        global_config: &Global,
+        terms: &LaunchTerms,
         params: &CreateBondingCurveParams,
         clock: &Clock,
         bump: u8,
@@ -68,19 +69,26 @@ impl BondingCurve {
         } else {
             clock.unix_timestamp
         };
-        let creator = creator;
-        let complete = false;
         self.clone_from(&BondingCurve {
             mint,
             creator,
-            virtual_token_reserves: global_config.initial_virtual_token_reserves,
-            virtual_sol_reserves: global_config.initial_virtual_sol_reserves,
-            initial_virtual_token_reserves: global_config.initial_virtual_token_reserves,
+            virtual_token_reserves: terms.curve.initial_virtual_token_reserves,
+            virtual_sol_reserves: terms.curve.initial_virtual_sol_reserves,
+            initial_virtual_token_reserves: terms.curve.initial_virtual_token_reserves,
             real_sol_reserves: 0,
-            real_token_reserves: global_config.initial_real_token_reserves,
-            token_total_supply: global_config.token_total_supply,
+            real_token_reserves: terms.curve.initial_real_token_reserves,
+            token_total_supply: terms.curve.token_total_supply,
             start_time,
-            complete,
+            status: CurveStatus::Pending,
+            pool: Pubkey::default(),
+            paused: false,
+            delisted: false,
+            version: CURRENT_VERSION,
+            launch_config: terms.launch_config,
+            initial_real_token_reserves: terms.curve.initial_real_token_reserves,
+            fee_schedule: terms.fees,
+            trade_count: 0,
+            reserved: [0; CURVE_RESERVED_BYTES],
             bump,
         });
         self
@@ -93,7 +101,8 @@ impl BondingCurve {
         let mut token_amount = self.get_tokens_for_buy_sol(sol_amount)?;
         msg!("ApplyBuy: token_amount: {}", token_amount);
 
-        if token_amount >= self.real_token_reserves {
+        let completes = token_amount >= self.real_token_reserves;
+        if completes {
             // Last Buy
             token_amount = self.real_token_reserves;
 
@@ -115,9 +124,6 @@ impl BondingCurve {
             // Restore the state with the recomputed sol_amount
             self.virtual_token_reserves = current_virtual_token_reserves;
             self.virtual_sol_reserves = current_virtual_sol_reserves;
-
-            // Set complete to true
-            self.complete = true;
         }
 
         // Adjusting token reserve values
@@ -159,6 +165,7 @@ impl BondingCurve {
         Some(BuyResult {
             token_amount,
             sol_amount,
+            completes,
         })
     }
 
@@ -166,7 +173,12 @@ impl BondingCurve {
         msg!("apply_sell: token_amount: {}", token_amount);
 
         // Computing Sol Amount out
-        let sol_amount = self.get_sol_for_sell_tokens(token_amount)?;
+        let mut sol_amount = self.get_sol_for_sell_tokens(token_amount)?;
+        if self.status == CurveStatus::Refunding {
+            // the reserve math rounds in the seller's favour, so the last refunds
+            // would otherwise ask for more SOL than the curve still holds
+            sol_amount = sol_amount.min(self.real_sol_reserves);
+        }
         msg!("apply_sell: sol_amount: {}", sol_amount);
 
         // Adjusting token reserve values
@@ -205,7 +217,7 @@ impl BondingCurve {
         self.virtual_token_reserves = new_virtual_token_reserves.try_into().ok()?;
         self.real_token_reserves = new_real_token_reserves.try_into().ok()?;
         self.virtual_sol_reserves = new_virtual_sol_reserves.try_into().ok()?;
-        self.real_sol_reserves = new_real_sol_reserves.try_into().ok()?;
+        self.real_sol_reserves = new_real_sol_reserves;
         self.msg();
         Some(SellResult {
             token_amount,
@@ -291,16 +303,218 @@ impl BondingCurve {
         Some(recv)
     }
 
+    /// Marginal price `virtual_sol_reserves / virtual_token_reserves` in lamports per token
+    /// base unit, scaled by `PRICE_SCALE`.
+    pub fn marginal_price(&self) -> Option<u128> {
+        price_from_reserves(self.virtual_sol_reserves, self.virtual_token_reserves)
+    }
+
+    /// Total supply valued at the marginal price, saturating at `u64::MAX`.
+    pub fn market_cap_lamports(&self) -> Option<u64> {
+        market_cap_from_reserves(
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+            self.token_total_supply,
+        )
+    }
+
+    /// Share of the launch's real token reserves sold, in bps. Reaches
+    /// `BASIS_POINTS_DIVISOR` when the curve completes.
+    pub fn progress_bps(&self, global: &Global) -> u64 {
+        let initial = self.launch_real_token_reserves(global);
+        if initial == 0 {
+            return BASIS_POINTS_DIVISOR;
+        }
+        let sold = initial.saturating_sub(self.real_token_reserves);
+        bps_mul(BASIS_POINTS_DIVISOR, sold, initial).unwrap_or(BASIS_POINTS_DIVISOR)
+    }
+
+    /// Deviation in bps of the pool price `token_a / token_b` from the curve's marginal price.
+    pub fn price_deviation_bps(&self, token_a_amount: u64, token_b_amount: u64) -> Option<u64> {
+        // compare a / b against vsr / vtr without dividing
+        let pool_price =
+            (token_a_amount as u128).checked_mul(self.virtual_token_reserves as u128)?;
+        let curve_price =
+            (self.virtual_sol_reserves as u128).checked_mul(token_b_amount as u128)?;
+        pool_price
+            .abs_diff(curve_price)
+            .checked_mul(BASIS_POINTS_DIVISOR as u128)?
+            .checked_div(curve_price)?
+            .try_into()
+            .ok()
+    }
+
+    pub fn get_pool_seed(
+        &self,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        max_deviation_bps: u64,
+    ) -> Result<PoolSeed> {
+        let deviation = self
+            .price_deviation_bps(token_a_amount, token_b_amount)
+            .ok_or(ContractError::ArithmeticError)?;
+        msg!("GetPoolSeed: deviation {} bps", deviation);
+        if deviation <= max_deviation_bps {
+            return Ok(PoolSeed {
+                token_a_amount,
+                token_b_amount,
+                excess_token_b: 0,
+            });
+        }
+
+        // tokens needed to pair all of token A at the curve price
+        let target_token_b: u64 = (token_a_amount as u128)
+            .checked_mul(self.virtual_token_reserves as u128)
+            .and_then(|v| v.checked_div(self.virtual_sol_reserves as u128))
+            .and_then(|v| v.try_into().ok())
+            .ok_or(ContractError::ArithmeticError)?;
+
+        let seed = if target_token_b <= token_b_amount {
+            PoolSeed {
+                token_a_amount,
+                token_b_amount: target_token_b,
+                excess_token_b: token_b_amount - target_token_b,
+            }
+        } else {
+            // not enough tokens, surplus SOL stays in the curve for the sweep
+            let target_token_a: u64 = (token_b_amount as u128)
+                .checked_mul(self.virtual_sol_reserves as u128)
+                .and_then(|v| v.checked_div(self.virtual_token_reserves as u128))
+                .and_then(|v| v.try_into().ok())
+                .ok_or(ContractError::ArithmeticError)?;
+            PoolSeed {
+                token_a_amount: target_token_a,
+                token_b_amount,
+                excess_token_b: 0,
+            }
+        };
+        let deviation = self
+            .price_deviation_bps(seed.token_a_amount, seed.token_b_amount)
+            .ok_or(ContractError::ArithmeticError)?;
+        msg!(
+            "GetPoolSeed: adjusted {:?}, deviation {} bps",
+            seed,
+            deviation
+        );
+        require_gte!(
+            max_deviation_bps,
+            deviation,
+            ContractError::PriceDeviationExceeded
+        );
+        Ok(seed)
+    }
+
+    pub fn transition(&mut self, to: CurveStatus, timestamp: i64) -> Result<CurveStatusEvent> {
+        let from = self.status;
+        require!(
+            from.can_transition_to(to),
+            ContractError::InvalidStatusTransition
+        );
+        self.status = to;
+        msg!("BondingCurve status: {:?} -> {:?}", from, to);
+        Ok(CurveStatusEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: self.mint,
+            from,
+            to,
+            timestamp,
+        })
+    }
+
+    /// Closes a refunding curve once every token it sold has been sold back.
+    pub fn close_refunded(&mut self, global: &Global, timestamp: i64) -> Result<CurveStatusEvent> {
+        if self.status == CurveStatus::Refunding {
+            require_gte!(
+                self.real_token_reserves,
+                self.launch_real_token_reserves(global),
+                ContractError::RefundsOutstanding
+            );
+        }
+        self.transition(CurveStatus::Closed, timestamp)
+    }
+
+    /// Counts a swap and returns its sequence number, starting at 1.
+    pub fn next_trade(&mut self) -> Result<u64> {
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticError)?;
+        Ok(self.trade_count)
+    }
+
+    /// Trade event of a swap on the curve, read from the curve after the trade and
+    /// `next_trade`. `amounts` is `[sol_amount, token_amount]`.
+    pub fn trade_event(
+        &self,
+        global: &Global,
+        user: Pubkey,
+        is_buy: bool,
+        amounts: [u64; 2],
+        price_before: u128,
+        fees: Vec<FeeShare>,
+    ) -> Result<TradeEvent> {
+        let clock = Clock::get()?;
+        let fee_lamports = fees
+            .iter()
+            .try_fold(0u64, |sum, share| sum.checked_add(share.lamports))
+            .ok_or(ContractError::ArithmeticError)?;
+        Ok(TradeEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            mint: self.mint,
+            sol_amount: amounts[0],
+            token_amount: amounts[1],
+            fee_lamports,
+            is_buy,
+            user,
+            timestamp: clock.unix_timestamp,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            venue: TradeVenue::BondingCurve,
+            trade_seq: self.trade_count,
+            slot: clock.slot,
+            price_before,
+            price_after: self
+                .marginal_price()
+                .ok_or(ContractError::ArithmeticError)?,
+            market_cap_lamports: self
+                .market_cap_lamports()
+                .ok_or(ContractError::ArithmeticError)?,
+            progress_bps: self.progress_bps(global),
+            fees,
+        })
+    }
+
     pub fn is_started(&self, clock: &Clock) -> bool {
         let now = clock.unix_timestamp;
         now >= self.start_time
     }
 
-    pub fn msg(&self) -> () {
+    pub fn msg(&self) {
         msg!("{:#?}", self);
     }
 
-    pub fn invariant<'info>(ctx: &mut BondingCurveLockerCtx<'info>) -> Result<()> {
+    /// Rejects swaps and migration steps on a paused or delisted curve.
+    pub fn require_not_halted(&self) -> Result<()> {
+        require!(!self.delisted, ContractError::CurveDelisted);
+        require!(!self.paused, ContractError::CurvePaused);
+        Ok(())
+    }
+
+    /// Applies the given flags, returns true if the curve was halted and no longer is.
+    pub fn set_halt_flags(&mut self, paused: Option<bool>, delisted: Option<bool>) -> bool {
+        let was_halted = self.paused || self.delisted;
+        if let Some(paused) = paused {
+            self.paused = paused;
+        }
+        if let Some(delisted) = delisted {
+            self.delisted = delisted;
+        }
+        was_halted && !self.paused && !self.delisted
+    }
+
+    pub fn invariant(ctx: &mut BondingCurveLockerCtx) -> Result<()> {
         let bonding_curve = &mut ctx.bonding_curve;
         let tkn_account = &mut ctx.bonding_curve_token_account;
         if tkn_account.owner != bonding_curve.key() {
@@ -311,12 +525,17 @@ impl BondingCurve {
 
         let lamports = bonding_curve.get_lamports();
         let mut tkn_balance = tkn_account.amount;
-        if (tkn_balance + ctx.global.initial_real_token_reserves) >= ctx.global.token_total_supply {
-            tkn_balance = tkn_balance.checked_add(ctx.global.initial_real_token_reserves).ok_or(ContractError::ArithmeticError)?.checked_sub(ctx.global.token_total_supply).ok_or(ContractError::ArithmeticError)?;
+        let initial_real_token_reserves = bonding_curve.launch_real_token_reserves(&ctx.global);
+        if (tkn_balance + initial_real_token_reserves) >= bonding_curve.token_total_supply {
+            tkn_balance = tkn_balance
+                .checked_add(initial_real_token_reserves)
+                .ok_or(ContractError::ArithmeticError)?
+                .checked_sub(bonding_curve.token_total_supply)
+                .ok_or(ContractError::ArithmeticError)?;
         }
 
         let rent_exemption_balance: u64 =
-            Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE as usize);
+            Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
         let bonding_curve_pool_lamports: u64 = lamports - rent_exemption_balance;
 
         // Ensure real sol reserves are equal to bonding curve pool lamports
@@ -331,11 +550,11 @@ impl BondingCurve {
         }
 
         // Ensure the virtual reserves are always positive
-        if bonding_curve.virtual_sol_reserves <= 0 {
+        if bonding_curve.virtual_sol_reserves == 0 {
             msg!("Invariant failed: virtual_sol_reserves <= 0");
             return Err(ContractError::BondingCurveInvariant.into());
         }
-        if bonding_curve.virtual_token_reserves <= 0 {
+        if bonding_curve.virtual_token_reserves == 0 {
             msg!("Invariant failed: virtual_token_reserves <= 0");
             return Err(ContractError::BondingCurveInvariant.into());
         }
@@ -350,12 +569,12 @@ impl BondingCurve {
         }
 
         // Ensure the bonding curve is complete only if real token reserves are zero
-        if bonding_curve.complete && bonding_curve.real_token_reserves != 0 {
+        if bonding_curve.status == CurveStatus::Complete && bonding_curve.real_token_reserves != 0 {
             msg!("Invariant failed: bonding curve marked as complete but real_token_reserves != 0");
             return Err(ContractError::BondingCurveInvariant.into());
         }
 
-        if !bonding_curve.complete && !tkn_account.is_frozen() {
+        if bonding_curve.status.is_active() && !tkn_account.is_frozen() {
             msg!("Active BondingCurve TokenAccount must always be frozen at the end");
             return Err(ContractError::BondingCurveInvariant.into());
         }
@@ -367,7 +586,7 @@ impl fmt::Display for BondingCurve {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "BondingCurve {{ creator: {:?}, initial_virtual_token_reserves: {:?}, virtual_sol_reserves: {:?}, virtual_token_reserves: {:?}, real_sol_reserves: {:?}, real_token_reserves: {:?}, token_total_supply: {:?}, start_time: {:?}, complete: {:?} }}",
+            "BondingCurve {{ creator: {:?}, initial_virtual_token_reserves: {:?}, virtual_sol_reserves: {:?}, virtual_token_reserves: {:?}, real_sol_reserves: {:?}, real_token_reserves: {:?}, token_total_supply: {:?}, start_time: {:?}, status: {:?} }}",
             self.creator,
             self.initial_virtual_token_reserves,
             self.virtual_sol_reserves,
@@ -376,7 +595,7 @@ impl fmt::Display for BondingCurve {
             self.real_token_reserves,
             self.token_total_supply,
             self.start_time,
-            self.complete
+            self.status
         )
     }
 }
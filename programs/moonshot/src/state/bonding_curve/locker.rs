@@ -15,12 +15,12 @@ pub struct BondingCurveLockerCtx<'info> {
     pub global: Box<Account<'info, Global>>,
 }
 impl BondingCurveLockerCtx<'_> {
-    fn get_signer<'a>(&self) -> [&[u8]; 3] {
+    fn get_signer(&self) -> [&[u8]; 3] {
         let signer: [&[u8]; 3] =
             BondingCurve::get_signer(&self.bonding_curve_bump, self.mint.to_account_info().key);
         signer
     }
-    pub fn lock_ata<'a>(&self) -> Result<()> {
+    pub fn lock_ata(&self) -> Result<()> {
         let signer = self.get_signer();
         let signer_seeds: &[&[&[u8]]; 1] = &[&signer[..]];
 
@@ -38,7 +38,7 @@ impl BondingCurveLockerCtx<'_> {
 
         Ok(())
     }
-    pub fn unlock_ata<'a>(&self) -> Result<()> {
+    pub fn unlock_ata(&self) -> Result<()> {
         let signer = self.get_signer();
         let signer_seeds: &[&[&[u8]]; 1] = &[&signer[..]];
 
@@ -108,6 +108,7 @@ impl BondingCurveLockerCtx<'_> {
 }
 
 pub trait IntoBondingCurveLockerCtx<'info> {
+    #[allow(clippy::wrong_self_convention)]
     fn into_bonding_curve_locker_ctx(&self, bonding_curve_bump: u8)
         -> BondingCurveLockerCtx<'info>;
 }
@@ -1,5 +1,8 @@
 pub mod curve;
 pub mod structs;
+#[allow(clippy::module_inception)]
 pub mod tests;
 pub use structs::*;
 pub mod locker;
+pub mod status;
+pub use status::*;
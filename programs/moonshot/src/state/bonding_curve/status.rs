@@ -0,0 +1,103 @@
+use crate::errors::ContractError;
+use anchor_lang::prelude::*;
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, Default, PartialEq, Eq,
+)]
+pub enum CurveStatus {
+    #[default]
+    Pending, // account created, supply not minted yet
+    Trading,   // open for swaps
+    Complete,  // all real tokens sold, waiting for migration
+    Migrated,  // pool created, LP not locked yet
+    LpLocked,  // LP locked or burned per the liquidity policy
+    Refunding, // trading stopped for good, holders sell back into the curve
+    Closed,    // every refunded token is back, only the residue can be swept
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveAction {
+    Swap,
+    RoutedSwap,
+    CreatePool,
+    LockPool,
+    Sweep,
+    ClaimLpFees,
+    Refund, // a sell into a refunding curve
+}
+
+impl CurveStatus {
+    pub const ALL: [CurveStatus; 7] = [
+        CurveStatus::Pending,
+        CurveStatus::Trading,
+        CurveStatus::Complete,
+        CurveStatus::Migrated,
+        CurveStatus::LpLocked,
+        CurveStatus::Refunding,
+        CurveStatus::Closed,
+    ];
+
+    /// Pending, Trading and Refunding curves hold a frozen, unsold token reserve.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            CurveStatus::Pending | CurveStatus::Trading | CurveStatus::Refunding
+        )
+    }
+
+    /// Swaps trade on the curve until it migrates, then against its pool. A refunding
+    /// curve takes sells only.
+    pub fn swap_action(&self) -> CurveAction {
+        if self.allows(CurveAction::RoutedSwap) {
+            CurveAction::RoutedSwap
+        } else if self.allows(CurveAction::Refund) {
+            CurveAction::Refund
+        } else {
+            CurveAction::Swap
+        }
+    }
+
+    pub fn allows(&self, action: CurveAction) -> bool {
+        matches!(
+            (action, self),
+            (CurveAction::Swap, CurveStatus::Trading)
+                | (CurveAction::RoutedSwap, CurveStatus::Migrated)
+                | (CurveAction::RoutedSwap, CurveStatus::LpLocked)
+                | (CurveAction::CreatePool, CurveStatus::Complete)
+                | (CurveAction::LockPool, CurveStatus::Migrated)
+                | (CurveAction::Sweep, CurveStatus::Migrated)
+                | (CurveAction::Sweep, CurveStatus::LpLocked)
+                | (CurveAction::Sweep, CurveStatus::Closed)
+                | (CurveAction::ClaimLpFees, CurveStatus::LpLocked)
+                | (CurveAction::Refund, CurveStatus::Refunding)
+        )
+    }
+
+    pub fn require(&self, action: CurveAction) -> Result<()> {
+        if self.allows(action) {
+            return Ok(());
+        }
+        msg!("{:?} not allowed while curve is {:?}", action, self);
+        match (action, self) {
+            (CurveAction::Swap, _) if !self.is_active() => {
+                Err(ContractError::BondingCurveComplete.into())
+            }
+            (CurveAction::CreatePool, _) if self.is_active() => {
+                Err(ContractError::BondingCurveNotComplete.into())
+            }
+            _ => Err(ContractError::InvalidCurveStatus.into()),
+        }
+    }
+
+    pub fn can_transition_to(&self, to: CurveStatus) -> bool {
+        matches!(
+            (self, to),
+            (CurveStatus::Pending, CurveStatus::Trading)
+                | (CurveStatus::Trading, CurveStatus::Complete)
+                | (CurveStatus::Complete, CurveStatus::Migrated)
+                | (CurveStatus::Migrated, CurveStatus::LpLocked)
+                | (CurveStatus::Trading, CurveStatus::Refunding)
+                | (CurveStatus::Refunding, CurveStatus::Closed)
+        )
+    }
+}
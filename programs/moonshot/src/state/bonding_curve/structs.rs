@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
 
+use super::status::CurveStatus;
+use crate::state::launch_config::FeeSchedule;
+use crate::state::versioned::{versioned_account, Versioned, CURRENT_VERSION, RESERVED_WORDS};
+
 #[derive(Debug, Clone)]
 pub struct BuyResult {
     pub token_amount: u64,
     pub sol_amount: u64,
+    pub completes: bool, // takes the last real tokens, the curve moves to Complete
 }
 
 #[derive(Debug, Clone)]
@@ -12,8 +17,15 @@ pub struct SellResult {
     pub sol_amount: u64,
 }
 
-#[account]
-#[derive(InitSpace, Debug, Default)]
+/// Initial pool liquidity, priced at the curve's final marginal price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolSeed {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub excess_token_b: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug, Default)]
 pub struct BondingCurve {
     pub mint: Pubkey,
     pub creator: Pubkey,
@@ -30,14 +42,101 @@ pub struct BondingCurve {
     pub token_total_supply: u64,
     // pub sol_launch_threshold: u64,
     pub start_time: i64,
-    pub complete: bool,
+    pub status: CurveStatus,
+    pub pool: Pubkey, // migrated pool, default until create_pool
+    pub paused: bool,   // per-curve emergency stop
+    pub delisted: bool, // removed from trading and migration
+
+    pub bump: u8,
+    pub version: u8,
+    // carved out of the reserved tail
+    pub launch_config: Pubkey, // default when created from the Global settings
+    pub initial_real_token_reserves: u64, // 0 on curves that predate launch configs
+    pub fee_schedule: FeeSchedule,
+    pub trade_count: u64, // swaps so far, the last TradeEvent carries the same number
+    pub reserved: [u8; CURVE_RESERVED_BYTES],
+}
+
+/// Fixed-point scale of curve prices.
+pub const PRICE_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Lamports per token base unit at the given reserves, scaled by `PRICE_SCALE`.
+pub fn price_from_reserves(sol_reserves: u64, token_reserves: u64) -> Option<u128> {
+    (sol_reserves as u128)
+        .checked_mul(PRICE_SCALE)?
+        .checked_div(token_reserves as u128)
+}
+
+/// `token_supply` valued at the reserves' price, saturating at `u64::MAX`.
+pub fn market_cap_from_reserves(
+    sol_reserves: u64,
+    token_reserves: u64,
+    token_supply: u64,
+) -> Option<u64> {
+    let market_cap = (sol_reserves as u128)
+        .checked_mul(token_supply as u128)?
+        .checked_div(token_reserves as u128)?;
+    Some(market_cap.try_into().unwrap_or(u64::MAX))
+}
+
+/// What is left of the reserved tail after the fields carved out of it.
+pub const CURVE_RESERVED_BYTES: usize = 8 * RESERVED_WORDS - 32 - 8 - FeeSchedule::INIT_SPACE - 8;
+
+versioned_account!(BondingCurve, [23, 183, 248, 55, 96, 216, 172, 96]);
 
+/// BondingCurve as written before the version field existed.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct BondingCurveV1 {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub start_time: i64,
+    pub complete: bool,
     pub bump: u8,
 }
+
+impl Versioned for BondingCurve {
+    const V1_SPACE: usize = 32 + 32 + 8 * 7 + 1 + 1;
+    type V1 = BondingCurveV1;
+
+    /// v1 curves were trading from creation until complete.
+    fn from_v1(v1: BondingCurveV1) -> Self {
+        BondingCurve {
+            mint: v1.mint,
+            creator: v1.creator,
+            initial_virtual_token_reserves: v1.initial_virtual_token_reserves,
+            virtual_sol_reserves: v1.virtual_sol_reserves,
+            virtual_token_reserves: v1.virtual_token_reserves,
+            real_sol_reserves: v1.real_sol_reserves,
+            real_token_reserves: v1.real_token_reserves,
+            token_total_supply: v1.token_total_supply,
+            start_time: v1.start_time,
+            status: if v1.complete {
+                CurveStatus::Complete
+            } else {
+                CurveStatus::Trading
+            },
+            bump: v1.bump,
+            version: CURRENT_VERSION,
+            ..Default::default()
+        }
+    }
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateBondingCurveParams {
     pub name: String,
     pub symbol: String,
     pub uri: String,
     pub start_time: Option<i64>,
+    pub launch_config: Option<u16>, // LaunchConfig index, None for the Global settings
+    pub whitelist_proof: Option<Vec<[u8; 32]>>, // merkle proof, instead of the Whitelist PDA
 }
\ No newline at end of file
@@ -2,9 +2,26 @@
 mod tests {
     use anchor_lang::prelude::{msg, Clock, Pubkey};
     use once_cell::sync::Lazy;
-    use structs::{BondingCurve, CreateBondingCurveParams};
+    use structs::{BondingCurve, CreateBondingCurveParams, PoolSeed, PRICE_SCALE};
 
-    use crate::{state::bonding_curve::*, util::bps_mul, Global};
+    use crate::{
+        constants::{METEORA_PROGRAM_KEY, QUOTE_MINT, VAULT_SEED},
+        errors::ContractError,
+        instructions::{LpLocker, PoolCreator},
+        state::{
+            bonding_curve::*,
+            global::ProgramStatus,
+            launch_config::FeeSchedule,
+            meteora::{get_lock_escrow_address, get_lock_lp_ix_data, get_lp_mint_address},
+            testing::{mint_data, token_account_data, TestAccount},
+        },
+        util::bps_mul,
+        Global,
+    };
+    use anchor_lang::prelude::{Account, Program};
+    use anchor_spl::associated_token::get_associated_token_address;
+    use anchor_spl::token::{self, Mint, Token, TokenAccount};
+    use std::str::FromStr;
     use std::time::{SystemTime, UNIX_EPOCH};
     static START_TIME: Lazy<i64> = Lazy::new(|| {
         SystemTime::now()
@@ -13,7 +30,7 @@ mod tests {
             .as_secs() as i64
     });
     static CLOCK: Lazy<Clock> = Lazy::new(|| Clock {
-        unix_timestamp: START_TIME.clone(),
+        unix_timestamp: *START_TIME,
         ..Clock::default()
     });
 
@@ -29,9 +46,11 @@ mod tests {
             symbol: "test".to_string(),
             uri: "test".to_string(),
             start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
         };
         let mut bc = BondingCurve::default();
-        let curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        let curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
         let curve_initial = curve.clone();
 
         // Attempt to buy more tokens than available in reserves
@@ -40,7 +59,9 @@ mod tests {
 
         assert_eq!(buy_result.token_amount, 793100000000000); // Max amount in curve
         assert_eq!(buy_result.sol_amount, 85007359056); // Should be max cost of curve
-        assert_eq!(curve.complete, true);
+        assert!(buy_result.completes);
+        // the swap handler moves the curve to Complete, not the curve math
+        assert_eq!(curve.status, curve_initial.status);
         assert_eq!(
             curve.real_token_reserves,
             curve_initial.real_token_reserves - buy_result.token_amount
@@ -101,6 +122,18 @@ mod tests {
         time_now = 300 * 400;
         let fee = bonding_curve.calculate_fee(1000, time_now).unwrap();
         assert_eq!(fee, 10); // 1% of 1000
+
+        // a v2 curve created before the schedule was recorded charges the default one
+        let unset = BondingCurve {
+            fee_schedule: FeeSchedule::UNSET,
+            ..BondingCurve::default()
+        };
+        for slot in [0, 150, 200, 250, 300] {
+            assert_eq!(
+                unset.calculate_fee(1000, slot * 400).unwrap(),
+                bonding_curve.calculate_fee(1000, slot * 400).unwrap()
+            );
+        }
     }
 
     #[test]
@@ -114,9 +147,11 @@ mod tests {
             symbol: "test".to_string(),
             uri: "test".to_string(),
             start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
         };
         let mut bc = BondingCurve::default();
-        let curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        let curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
 
         // first apply buy
         curve.apply_buy(1000000000).unwrap(); // 1 SOL
@@ -157,9 +192,11 @@ mod tests {
             symbol: "test".to_string(),
             uri: "test".to_string(),
             start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
         };
         let mut bc = BondingCurve::default();
-        let curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        let curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
         let curve_initial = curve.clone();
 
         let purchase_amount = 1000000000; // 1 SOL
@@ -194,9 +231,11 @@ mod tests {
             symbol: "test".to_string(),
             uri: "test".to_string(),
             start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
         };
         let mut bc = BondingCurve::default();
-        let curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        let curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
 
         // first apply 1 SOL buy
         let buy_result = curve.apply_buy(1000000000).unwrap();
@@ -233,15 +272,17 @@ mod tests {
             symbol: "test".to_string(),
             uri: "test".to_string(),
             start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
         };
         let mut bc = BondingCurve::default();
-        let mut curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        let mut curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
 
         // Test case 1: Normal case 0.01 SOL SOL
         assert_eq!(curve.get_tokens_for_buy_sol(10000000), Some(357548000000));
 
         // Test case 2: Normal case 1 SOL SOL
-        curve = bc.update_from_params(mint, creator, &global, &params, &CLOCK, 0);
+        curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
         assert_eq!(
             curve.get_tokens_for_buy_sol(1000000000),
             Some(34612904000000)
@@ -264,6 +305,672 @@ mod tests {
         // );
     }
 
+    #[test]
+    fn test_status_action_matrix() {
+        use CurveAction::*;
+        use CurveStatus::*;
+        let allowed = [
+            (Swap, Trading),
+            (RoutedSwap, Migrated),
+            (RoutedSwap, LpLocked),
+            (CreatePool, Complete),
+            (LockPool, Migrated),
+            (Sweep, Migrated),
+            (Sweep, LpLocked),
+            (Sweep, Closed),
+            (ClaimLpFees, LpLocked),
+            (Refund, Refunding),
+        ];
+
+        for action in [
+            Swap,
+            RoutedSwap,
+            CreatePool,
+            LockPool,
+            Sweep,
+            ClaimLpFees,
+            Refund,
+        ] {
+            for status in CurveStatus::ALL {
+                let expected = allowed.contains(&(action, status));
+                assert_eq!(
+                    status.allows(action),
+                    expected,
+                    "{:?} in {:?}",
+                    action,
+                    status
+                );
+                assert_eq!(
+                    status.require(action).is_ok(),
+                    expected,
+                    "{:?} in {:?}",
+                    action,
+                    status
+                );
+            }
+        }
+
+        assert_eq!(
+            Complete.require(Swap).unwrap_err(),
+            ContractError::BondingCurveComplete.into()
+        );
+        assert_eq!(
+            Trading.require(CreatePool).unwrap_err(),
+            ContractError::BondingCurveNotComplete.into()
+        );
+        assert_eq!(
+            LpLocked.require(CreatePool).unwrap_err(),
+            ContractError::InvalidCurveStatus.into()
+        );
+
+        assert_eq!(
+            Refunding.require(CreatePool).unwrap_err(),
+            ContractError::BondingCurveNotComplete.into()
+        );
+
+        for status in CurveStatus::ALL {
+            let expected = match status {
+                Migrated | LpLocked => RoutedSwap,
+                Refunding => Refund,
+                _ => Swap,
+            };
+            assert_eq!(status.swap_action(), expected, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let creator = Pubkey::default();
+        let mint = Pubkey::default();
+        let global = Global::default();
+
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let mut bc = BondingCurve::default();
+        let curve = bc.update_from_params(mint, creator, &global.launch_terms(), &params, &CLOCK, 0);
+        assert_eq!(curve.status, CurveStatus::Pending);
+
+        // skipping a step is rejected and leaves the status untouched
+        assert!(curve
+            .transition(CurveStatus::Migrated, *START_TIME)
+            .is_err());
+        assert_eq!(curve.status, CurveStatus::Pending);
+
+        for to in [
+            CurveStatus::Trading,
+            CurveStatus::Complete,
+            CurveStatus::Migrated,
+            CurveStatus::LpLocked,
+        ] {
+            let from = curve.status;
+            let event = curve.transition(to, *START_TIME).unwrap();
+            assert_eq!((event.from, event.to), (from, to));
+            assert_eq!(curve.status, to);
+        }
+
+        // a completed curve cannot be refunded
+        assert!(!CurveStatus::Complete.can_transition_to(CurveStatus::Refunding));
+
+        // no way back from a terminal status
+        for to in CurveStatus::ALL {
+            assert!(!CurveStatus::LpLocked.can_transition_to(to));
+            assert!(!CurveStatus::Closed.can_transition_to(to));
+        }
+    }
+
+    #[test]
+    fn test_refund_and_close() {
+        let global = Global::default();
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let mut bc = BondingCurve::default();
+        let curve = bc.update_from_params(
+            Pubkey::default(),
+            Pubkey::default(),
+            &global.launch_terms(),
+            &params,
+            &CLOCK,
+            0,
+        );
+        curve.transition(CurveStatus::Trading, *START_TIME).unwrap();
+        let buy = curve.apply_buy(1_000_000_000).unwrap();
+
+        // only a refunding curve can be closed
+        assert_eq!(
+            curve
+                .close_refunded(&global, *START_TIME)
+                .map(|_| ())
+                .unwrap_err(),
+            ContractError::InvalidStatusTransition.into()
+        );
+
+        let event = curve
+            .transition(CurveStatus::Refunding, *START_TIME)
+            .unwrap();
+        assert_eq!(
+            (event.from, event.to),
+            (CurveStatus::Trading, CurveStatus::Refunding)
+        );
+        assert_eq!(curve.status.swap_action(), CurveAction::Refund);
+
+        // the buyer still holds what it bought
+        assert_eq!(
+            curve
+                .close_refunded(&global, *START_TIME)
+                .map(|_| ())
+                .unwrap_err(),
+            ContractError::RefundsOutstanding.into()
+        );
+        assert_eq!(curve.status, CurveStatus::Refunding);
+
+        curve.apply_sell(buy.token_amount).unwrap();
+        assert_eq!(curve.real_sol_reserves, 0);
+        let event = curve.close_refunded(&global, *START_TIME).unwrap();
+        assert_eq!(
+            (event.from, event.to),
+            (CurveStatus::Refunding, CurveStatus::Closed)
+        );
+        assert!(curve.status.allows(CurveAction::Sweep));
+    }
+
+    fn complete_curve(global: &Global) -> BondingCurve {
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: Some(*START_TIME),
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let mut bc = BondingCurve::default();
+        bc.update_from_params(
+            Pubkey::default(),
+            Pubkey::default(),
+            &global.launch_terms(),
+            &params,
+            &CLOCK,
+            0,
+        );
+        assert!(bc.apply_buy(2000000000000000000).unwrap().completes);
+        bc.transition(CurveStatus::Trading, *START_TIME).unwrap();
+        bc.transition(CurveStatus::Complete, *START_TIME).unwrap();
+        bc
+    }
+
+    /// A program-owned global and curve in `status`, with the curve trading `mint`.
+    fn migration_accounts(status: CurveStatus, mint: Pubkey) -> (TestAccount, TestAccount) {
+        let global = Global {
+            status: ProgramStatus::Running,
+            ..Default::default()
+        };
+        let curve = BondingCurve {
+            mint,
+            status,
+            ..Default::default()
+        };
+        (
+            TestAccount::program_owned(&global),
+            TestAccount::program_owned(&curve),
+        )
+    }
+
+    #[test]
+    fn test_lock_pool_status_gate() {
+        for status in CurveStatus::ALL {
+            let (mut global, mut curve) = migration_accounts(status, Pubkey::new_unique());
+            let mut payer_pool_lp = TestAccount::new(
+                token::ID,
+                token_account_data(Pubkey::new_unique(), Pubkey::new_unique(), 0),
+            );
+            let mut unchecked = TestAccount::new(Pubkey::default(), vec![]);
+            let global_info = global.info();
+            let curve_info = curve.info();
+            let global = Account::<Global>::try_from(&global_info).unwrap();
+            let mut bonding_curve = Account::<BondingCurve>::try_from(&curve_info).unwrap();
+            let unchecked = unchecked.info();
+
+            let mut locker = LpLocker {
+                global: &global,
+                bonding_curve: &mut bonding_curve,
+                vault: unchecked.clone(),
+                vault_bump: 0,
+                pool: unchecked.clone(),
+                lp_mint: unchecked.clone(),
+                a_vault_lp: unchecked.clone(),
+                b_vault_lp: unchecked.clone(),
+                token_b_mint: unchecked.clone(),
+                a_vault: unchecked.clone(),
+                b_vault: unchecked.clone(),
+                a_vault_lp_mint: unchecked.clone(),
+                b_vault_lp_mint: unchecked.clone(),
+                payer_pool_lp: payer_pool_lp.info(),
+                vault_pool_lp: unchecked.clone(),
+                payer: unchecked.clone(),
+                token_program: unchecked.clone(),
+                associated_token_program: unchecked.clone(),
+                system_program: unchecked.clone(),
+                lock_escrow: unchecked.clone(),
+                escrow_vault: unchecked,
+            };
+
+            // a migrated curve gets past the status gate and stops at the empty LP account
+            let expected = if status == CurveStatus::Migrated {
+                ContractError::ZeroLpAmount
+            } else {
+                ContractError::InvalidCurveStatus
+            };
+            assert_eq!(
+                locker.lock_pool().map(|_| ()).unwrap_err(),
+                expected.into(),
+                "{:?}",
+                status
+            );
+            assert_eq!(locker.bonding_curve.status, status);
+        }
+    }
+
+    #[test]
+    fn test_lock_pool_escrow_accounts() {
+        let pool = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let lp_mint = get_lp_mint_address(&pool);
+        let (vault, vault_bump) = Pubkey::find_program_address(&[VAULT_SEED], &crate::ID);
+        let lock_escrow = get_lock_escrow_address(&pool, &vault);
+        let vault_pool_lp = get_associated_token_address(&vault, &lp_mint);
+        let escrow_vault = get_associated_token_address(&lock_escrow, &lp_mint);
+        let payer_pool_lp = get_associated_token_address(&payer, &lp_mint);
+
+        let (mut global, mut curve) =
+            migration_accounts(CurveStatus::Migrated, Pubkey::new_unique());
+        let at = |key: Pubkey| TestAccount {
+            key,
+            ..TestAccount::new(Pubkey::default(), vec![])
+        };
+        // [vault, pool, lp_mint, payer, payer_pool_lp, vault_pool_lp, lock_escrow, escrow_vault,
+        //  token_program, system_program, pool vaults..]
+        let mut accounts: Vec<TestAccount> = [
+            vault,
+            pool,
+            lp_mint,
+            payer,
+            payer_pool_lp,
+            vault_pool_lp,
+            lock_escrow,
+            escrow_vault,
+            token::ID,
+            anchor_lang::system_program::ID,
+        ]
+        .into_iter()
+        .map(at)
+        .chain((0..7).map(|_| at(Pubkey::new_unique())))
+        .collect();
+        let global_info = global.info();
+        let curve_info = curve.info();
+        let global = Account::<Global>::try_from(&global_info).unwrap();
+        let mut bonding_curve = Account::<BondingCurve>::try_from(&curve_info).unwrap();
+        let infos: Vec<_> = accounts.iter_mut().map(|account| account.info()).collect();
+
+        let locker = LpLocker {
+            global: &global,
+            bonding_curve: &mut bonding_curve,
+            vault: infos[0].clone(),
+            vault_bump,
+            pool: infos[1].clone(),
+            lp_mint: infos[2].clone(),
+            payer: infos[3].clone(),
+            payer_pool_lp: infos[4].clone(),
+            vault_pool_lp: infos[5].clone(),
+            lock_escrow: infos[6].clone(),
+            escrow_vault: infos[7].clone(),
+            token_program: infos[8].clone(),
+            system_program: infos[9].clone(),
+            a_vault: infos[10].clone(),
+            b_vault: infos[11].clone(),
+            a_vault_lp: infos[12].clone(),
+            b_vault_lp: infos[13].clone(),
+            a_vault_lp_mint: infos[14].clone(),
+            b_vault_lp_mint: infos[15].clone(),
+            token_b_mint: infos[16].clone(),
+            associated_token_program: infos[16].clone(),
+        };
+        // lock_liquidity signs with these seeds, so the owner must be the VAULT_SEED PDA
+        assert_eq!(
+            Pubkey::create_program_address(&[VAULT_SEED, &[locker.vault_bump]], &crate::ID),
+            Ok(vault)
+        );
+
+        // create_lock_escrow: [pool, lock_escrow, owner, lp_mint, payer, system_program]
+        let escrow_ix = locker.create_lock_escrow_instruction();
+        assert_eq!(
+            escrow_ix.program_id,
+            Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap()
+        );
+        let metas = &escrow_ix.accounts;
+        assert_eq!(metas[0].pubkey, pool);
+        assert_eq!(metas[1].pubkey, get_lock_escrow_address(&pool, &vault));
+        assert_eq!(metas[2].pubkey, vault);
+        assert!(!metas[2].is_signer);
+        assert_eq!(metas[4].pubkey, payer);
+        assert!(metas[4].is_signer);
+
+        // lock: [pool, lp_mint, lock_escrow, owner, source_tokens, escrow_vault, token_program,
+        //  a_vault, b_vault, a_vault_lp, b_vault_lp, a_vault_lp_mint, b_vault_lp_mint]
+        let lock_ix = locker.lock_instruction(750);
+        assert_eq!(lock_ix.program_id, escrow_ix.program_id);
+        assert_eq!(lock_ix.data, get_lock_lp_ix_data(750));
+        let metas = &lock_ix.accounts;
+        assert_eq!(metas.len(), 13);
+        assert_eq!(metas[1].pubkey, lp_mint);
+        assert_eq!(metas[2].pubkey, get_lock_escrow_address(&pool, &vault));
+        assert_eq!(metas[3].pubkey, vault);
+        assert!(metas[3].is_signer);
+        assert_eq!(metas[4].pubkey, vault_pool_lp);
+        assert_eq!(metas[5].pubkey, escrow_vault);
+        assert_eq!(metas[6].pubkey, token::ID);
+        for (meta, info) in metas[7..].iter().zip(&infos[10..16]) {
+            assert_eq!(meta.pubkey, *info.key);
+        }
+        // the vault is the only signer, the migration signer plays no part in the lock
+        assert_eq!(metas.iter().filter(|meta| meta.is_signer).count(), 1);
+        assert!(!metas
+            .iter()
+            .any(|meta| meta.pubkey == payer || meta.pubkey == payer_pool_lp));
+
+        // the event only names the escrow when LP went into it
+        assert_eq!(
+            locker.policy_event(1_000, 750, 250, 0).lock_escrow,
+            lock_escrow
+        );
+        assert_eq!(
+            locker.policy_event(1_000, 0, 1_000, 0).lock_escrow,
+            Pubkey::default()
+        );
+    }
+
+    #[test]
+    fn test_create_pool_status_gate() {
+        for status in CurveStatus::ALL {
+            let mut token_b_mint = TestAccount::new(token::ID, mint_data(0));
+            let (mut global, mut curve) = migration_accounts(status, token_b_mint.key);
+            let mut curve_tokens = TestAccount::new(
+                token::ID,
+                token_account_data(token_b_mint.key, Pubkey::new_unique(), 0),
+            );
+            let mut token_program = TestAccount::program(token::ID);
+            let mut token_a_mint = TestAccount {
+                key: Pubkey::from_str(QUOTE_MINT).unwrap(),
+                ..TestAccount::new(token::ID, mint_data(0))
+            };
+            // not the meteora config of the global
+            let mut config = TestAccount::new(Pubkey::new_unique(), vec![]);
+            let mut unchecked = TestAccount::new(Pubkey::default(), vec![]);
+            let global_info = global.info();
+            let curve_info = curve.info();
+            let mint_info = token_b_mint.info();
+            let curve_tokens_info = curve_tokens.info();
+            let token_program_info = token_program.info();
+            let global = Account::<Global>::try_from(&global_info).unwrap();
+            let mut bonding_curve = Account::<BondingCurve>::try_from(&curve_info).unwrap();
+            let token_b_mint = Account::<Mint>::try_from(&mint_info).unwrap();
+            let curve_tokens = Account::<TokenAccount>::try_from(&curve_tokens_info).unwrap();
+            let token_program = Program::<Token>::try_from(&token_program_info).unwrap();
+            let unchecked = unchecked.info();
+
+            let mut creator = PoolCreator {
+                global: &global,
+                bonding_curve: &mut bonding_curve,
+                bonding_curve_bump: 0,
+                launch_config: None,
+                token_b_mint: &token_b_mint,
+                bonding_curve_token_account: &curve_tokens,
+                treasury_token_account: None,
+                token_program: &token_program,
+                migration_vault: unchecked.clone(),
+                pool: unchecked.clone(),
+                config: config.info(),
+                lp_mint: unchecked.clone(),
+                a_vault_lp: unchecked.clone(),
+                b_vault_lp: unchecked.clone(),
+                token_a_mint: token_a_mint.info(),
+                a_vault: unchecked.clone(),
+                b_vault: unchecked.clone(),
+                a_token_vault: unchecked.clone(),
+                b_token_vault: unchecked.clone(),
+                a_vault_lp_mint: unchecked.clone(),
+                b_vault_lp_mint: unchecked.clone(),
+                payer_token_a: unchecked.clone(),
+                payer_token_b: unchecked.clone(),
+                payer_pool_lp: unchecked.clone(),
+                protocol_token_a_fee: unchecked.clone(),
+                protocol_token_b_fee: unchecked.clone(),
+                payer: unchecked.clone(),
+                mint_metadata: unchecked.clone(),
+                rent: unchecked.clone(),
+                metadata_program: unchecked.clone(),
+                vault_program: unchecked.clone(),
+                associated_token_program: unchecked.clone(),
+                system_program: unchecked,
+            };
+
+            // a complete curve gets past the status gate and stops at the config check
+            let expected = match status {
+                CurveStatus::Complete => ContractError::InvalidConfig,
+                CurveStatus::Pending | CurveStatus::Trading | CurveStatus::Refunding => {
+                    ContractError::BondingCurveNotComplete
+                }
+                CurveStatus::Migrated | CurveStatus::LpLocked | CurveStatus::Closed => {
+                    ContractError::InvalidCurveStatus
+                }
+            };
+            assert_eq!(
+                creator.create_pool(&[]).map(|_| ()).unwrap_err(),
+                expected.into(),
+                "{:?}",
+                status
+            );
+            assert_eq!(creator.bonding_curve.status, status);
+        }
+    }
+
+    fn available_pool_amounts(global: &Global, curve: &BondingCurve) -> (u64, u64) {
+        (
+            curve.real_sol_reserves - global.migrate_fee_amount - global.migration_reserve_lamports,
+            global.token_total_supply - global.initial_real_token_reserves,
+        )
+    }
+
+    #[test]
+    fn test_halt_flags() {
+        let mut curve = BondingCurve::default();
+        assert!(curve.require_not_halted().is_ok());
+
+        assert!(!curve.set_halt_flags(Some(true), None));
+        assert_eq!(
+            curve.require_not_halted().unwrap_err(),
+            ContractError::CurvePaused.into()
+        );
+        // delisting takes precedence, lifting only one flag keeps the curve halted
+        assert!(!curve.set_halt_flags(None, Some(true)));
+        assert_eq!(
+            curve.require_not_halted().unwrap_err(),
+            ContractError::CurveDelisted.into()
+        );
+        assert!(!curve.set_halt_flags(Some(false), None));
+        assert!(curve.require_not_halted().is_err());
+
+        assert!(curve.set_halt_flags(None, Some(false)));
+        assert!(curve.require_not_halted().is_ok());
+        // no-op updates on a live curve are not a lift
+        assert!(!curve.set_halt_flags(Some(false), Some(false)));
+    }
+
+    #[test]
+    fn test_price_market_cap_and_progress() {
+        let global = Global::default();
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: None,
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let mut curve = BondingCurve::default();
+        curve.update_from_params(
+            Pubkey::default(),
+            Pubkey::default(),
+            &global.launch_terms(),
+            &params,
+            &CLOCK,
+            0,
+        );
+        assert_eq!(curve.progress_bps(&global), 0);
+
+        let price = curve.marginal_price().unwrap();
+        assert_eq!(
+            price,
+            curve.virtual_sol_reserves as u128 * PRICE_SCALE / curve.virtual_token_reserves as u128
+        );
+        // market cap is the total supply at the marginal price, up to rounding
+        let market_cap = curve.market_cap_lamports().unwrap();
+        let at_price = price * curve.token_total_supply as u128 / PRICE_SCALE;
+        assert!(at_price.abs_diff(market_cap as u128) <= 1);
+
+        let initial_real = curve.real_token_reserves;
+        let buy = curve.apply_buy(1_000_000_000).unwrap();
+        assert!(!buy.completes);
+        let price_after_buy = curve.marginal_price().unwrap();
+        assert!(price_after_buy > price);
+        assert!(curve.market_cap_lamports().unwrap() > market_cap);
+        assert_eq!(
+            curve.progress_bps(&global),
+            buy.token_amount * 10_000 / initial_real
+        );
+
+        let progress_after_buy = curve.progress_bps(&global);
+        curve.apply_sell(buy.token_amount / 2).unwrap();
+        assert!(curve.marginal_price().unwrap() < price_after_buy);
+        assert!(curve.progress_bps(&global) < progress_after_buy);
+
+        let last_buy = curve.apply_buy(2_000_000_000_000_000_000).unwrap();
+        assert!(last_buy.completes);
+        assert_eq!(curve.progress_bps(&global), 10_000);
+    }
+
+    #[test]
+    fn test_trade_sequence() {
+        let mut curve = BondingCurve::default();
+        assert_eq!(curve.next_trade().unwrap(), 1);
+        assert_eq!(curve.next_trade().unwrap(), 2);
+        assert_eq!(curve.trade_count, 2);
+
+        // recreating from params starts a new sequence
+        let params = CreateBondingCurveParams {
+            name: "test".to_string(),
+            symbol: "test".to_string(),
+            uri: "test".to_string(),
+            start_time: None,
+            launch_config: None,
+            whitelist_proof: None,
+        };
+        let global = Global::default();
+        curve.update_from_params(
+            Pubkey::default(),
+            Pubkey::default(),
+            &global.launch_terms(),
+            &params,
+            &CLOCK,
+            0,
+        );
+        assert_eq!(curve.trade_count, 0);
+
+        curve.trade_count = u64::MAX;
+        assert_eq!(
+            curve.next_trade().unwrap_err(),
+            ContractError::ArithmeticError.into()
+        );
+    }
+
+    #[test]
+    fn test_pool_seed_default_params() {
+        let global = Global::default();
+        let curve = complete_curve(&global);
+        let (token_a, token_b) = available_pool_amounts(&global, &curve);
+
+        let deviation = curve.price_deviation_bps(token_a, token_b).unwrap();
+        println!("default deviation: {} bps", deviation);
+        assert!(deviation <= global.max_price_deviation_bps);
+
+        // within tolerance the pool is seeded with everything that is available
+        let seed = curve
+            .get_pool_seed(token_a, token_b, global.max_price_deviation_bps)
+            .unwrap();
+        assert_eq!(
+            seed,
+            PoolSeed {
+                token_a_amount: token_a,
+                token_b_amount: token_b,
+                excess_token_b: 0,
+            }
+        );
+
+        // a zero tolerance forces the amounts onto the curve price
+        let seed = curve.get_pool_seed(token_a, token_b, 0).unwrap();
+        assert_eq!(
+            curve.price_deviation_bps(seed.token_a_amount, seed.token_b_amount),
+            Some(0)
+        );
+        assert_eq!(seed.token_b_amount + seed.excess_token_b, token_b);
+        assert!(seed.token_a_amount <= token_a);
+    }
+
+    #[test]
+    fn test_pool_seed_global_param_sets() {
+        let excess_supply = Global {
+            token_total_supply: 1_200_000_000_000_000,
+            ..Global::default()
+        };
+        let short_supply = Global {
+            token_total_supply: 900_000_000_000_000,
+            ..Global::default()
+        };
+        let large_reserve = Global {
+            migration_reserve_lamports: 10_000_000_000,
+            ..Global::default()
+        };
+
+        for global in [excess_supply, short_supply, large_reserve] {
+            let curve = complete_curve(&global);
+            let (token_a, token_b) = available_pool_amounts(&global, &curve);
+            let deviation = curve.price_deviation_bps(token_a, token_b).unwrap();
+            println!("deviation: {} bps", deviation);
+            assert!(deviation > global.max_price_deviation_bps);
+
+            let seed = curve
+                .get_pool_seed(token_a, token_b, global.max_price_deviation_bps)
+                .unwrap();
+            println!("{:?}", seed);
+            let adjusted = curve
+                .price_deviation_bps(seed.token_a_amount, seed.token_b_amount)
+                .unwrap();
+            assert!(adjusted <= global.max_price_deviation_bps);
+            assert!(seed.token_a_amount <= token_a);
+            assert_eq!(seed.token_b_amount + seed.excess_token_b, token_b);
+        }
+    }
+
     // FUZZ TESTS
     use proptest::prelude::*;
 
@@ -283,9 +990,11 @@ mod tests {
                 symbol: "test".to_string(),
                 uri: "test".to_string(),
                 start_time: Some(*START_TIME),
+                launch_config: None,
+                whitelist_proof: None,
             };
             let mut bc = BondingCurve::default();
-            let curve = bc.update_from_params(mint,creator, &global, &params, &CLOCK, 0);
+            let curve = bc.update_from_params(mint,creator, &global.launch_terms(), &params, &CLOCK, 0);
             let _curve_initial = curve.clone();
 
             if let Some(result) = curve.apply_buy(sol_amount) {
@@ -307,9 +1016,11 @@ mod tests {
                 symbol: "test".to_string(),
                 uri: "test".to_string(),
                 start_time: Some(*START_TIME),
+                launch_config: None,
+                whitelist_proof: None,
             };
             let mut bc = BondingCurve::default();
-            let curve = bc.update_from_params(mint,creator, &global, &params, &CLOCK, 0);
+            let curve = bc.update_from_params(mint,creator, &global.launch_terms(), &params, &CLOCK, 0);
             let buy_result = curve.apply_buy(buy_sol_amount);
             if buy_result.is_none() {
                 return Ok(())
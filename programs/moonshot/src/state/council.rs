@@ -0,0 +1,237 @@
+use crate::errors::ContractError;
+use crate::state::global::{GlobalSettingsInput, Role};
+use anchor_lang::prelude::*;
+
+pub const MAX_COUNCIL_SIGNERS: usize = 10;
+
+/// M-of-N admin council. Becomes useful once its PDA is the global authority.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Council {
+    #[max_len(MAX_COUNCIL_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub generation: u64, // bumped on membership changes, invalidates open proposals
+}
+
+/// Admin action carried by a proposal, applied once enough signers approved it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+#[allow(clippy::large_enum_variant)] // lives in a Proposal account sized for the largest variant
+pub enum AdminAction {
+    SetParams(GlobalSettingsInput),
+    AddWl {
+        creator: Pubkey,
+    },
+    RemoveWl {
+        creator: Pubkey,
+    },
+    Pause,
+    Withdraw {
+        receiver: Pubkey,
+        amount: u64,
+    }, // lamports held by the council account
+    AcceptAuthority,
+    SetRole {
+        role: Role,
+        holder: Pubkey, // default revokes
+    },
+    UpdateCouncil {
+        #[max_len(MAX_COUNCIL_SIGNERS)]
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Proposal {
+    pub index: u64,
+    pub generation: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: u16, // bitmask over council.signers
+    pub executed: bool,
+    pub created_at: i64,
+}
+
+impl Council {
+    pub const SEED_PREFIX: &'static str = "council";
+
+    pub fn validate_members(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_COUNCIL_SIGNERS,
+            ContractError::InvalidCouncil
+        );
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            ContractError::InvalidCouncil
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[i + 1..].contains(signer),
+                ContractError::InvalidCouncil
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_members(&mut self, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        Self::validate_members(&signers, threshold)?;
+        self.signers = signers;
+        self.threshold = threshold;
+        self.generation = self.generation.wrapping_add(1);
+        Ok(())
+    }
+
+    pub fn signer_index(&self, signer: &Pubkey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|s| s == signer)
+            .ok_or(ContractError::NotCouncilSigner.into())
+    }
+
+    /// Records `signer`'s approval, returns the approval count.
+    pub fn approve(&self, proposal: &mut Proposal, signer: &Pubkey) -> Result<u8> {
+        self.require_open(proposal)?;
+        let bit = 1u16 << self.signer_index(signer)?;
+        require!(
+            proposal.approvals & bit == 0,
+            ContractError::AlreadyApproved
+        );
+        proposal.approvals |= bit;
+        Ok(proposal.approvals.count_ones() as u8)
+    }
+
+    /// Marks the proposal executed if it reached the threshold.
+    pub fn execute(&self, proposal: &mut Proposal) -> Result<()> {
+        self.require_open(proposal)?;
+        require_gte!(
+            proposal.approvals.count_ones() as u8,
+            self.threshold,
+            ContractError::ThresholdNotMet
+        );
+        proposal.executed = true;
+        Ok(())
+    }
+
+    fn require_open(&self, proposal: &Proposal) -> Result<()> {
+        require!(!proposal.executed, ContractError::ProposalAlreadyExecuted);
+        require_eq!(
+            proposal.generation,
+            self.generation,
+            ContractError::StaleProposal
+        );
+        Ok(())
+    }
+}
+
+impl Proposal {
+    pub const SEED_PREFIX: &'static str = "proposal";
+}
+
+impl AdminAction {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            AdminAction::UpdateCouncil { signers, threshold } => {
+                Council::validate_members(signers, *threshold).is_ok()
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn council(n: usize, threshold: u8) -> Council {
+        Council {
+            signers: (0..n).map(|_| Pubkey::new_unique()).collect(),
+            threshold,
+            proposal_count: 0,
+            generation: 0,
+        }
+    }
+
+    fn proposal(council: &Council, action: AdminAction) -> Proposal {
+        Proposal {
+            index: council.proposal_count,
+            generation: council.generation,
+            proposer: council.signers[0],
+            action,
+            approvals: 0,
+            executed: false,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_council_members() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        assert!(Council::validate_members(&[a, b], 2).is_ok());
+        assert!(Council::validate_members(&[a, b], 3).is_err());
+        assert!(Council::validate_members(&[a, b], 0).is_err());
+        assert!(Council::validate_members(&[], 0).is_err());
+        assert!(Council::validate_members(&[a, a], 1).is_err());
+        let too_many: Vec<Pubkey> = (0..=MAX_COUNCIL_SIGNERS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert!(Council::validate_members(&too_many, 1).is_err());
+    }
+
+    #[test]
+    fn test_proposal_threshold() {
+        let council = council(3, 2);
+        let mut proposal = proposal(&council, AdminAction::Pause);
+
+        assert!(council.execute(&mut proposal).is_err());
+        assert_eq!(
+            council.approve(&mut proposal, &council.signers[0]).unwrap(),
+            1
+        );
+        assert!(council.approve(&mut proposal, &council.signers[0]).is_err());
+        assert!(council
+            .approve(&mut proposal, &Pubkey::new_unique())
+            .is_err());
+        assert!(council.execute(&mut proposal).is_err());
+
+        assert_eq!(
+            council.approve(&mut proposal, &council.signers[2]).unwrap(),
+            2
+        );
+        council.execute(&mut proposal).unwrap();
+        assert!(proposal.executed);
+        assert!(council.execute(&mut proposal).is_err());
+        assert!(council.approve(&mut proposal, &council.signers[1]).is_err());
+    }
+
+    #[test]
+    fn test_membership_change_invalidates_proposals() {
+        let mut council = council(2, 1);
+        let mut proposal = proposal(&council, AdminAction::Pause);
+        council.approve(&mut proposal, &council.signers[0]).unwrap();
+
+        let signers = council.signers.clone();
+        council.set_members(signers, 1).unwrap();
+        assert!(council.execute(&mut proposal).is_err());
+        assert!(council.set_members(vec![], 1).is_err());
+    }
+
+    #[test]
+    fn test_admin_action_validity() {
+        let signer = Pubkey::new_unique();
+        assert!(AdminAction::Pause.is_valid());
+        assert!(AdminAction::UpdateCouncil {
+            signers: vec![signer],
+            threshold: 1
+        }
+        .is_valid());
+        assert!(!AdminAction::UpdateCouncil {
+            signers: vec![signer],
+            threshold: 2
+        }
+        .is_valid());
+    }
+}
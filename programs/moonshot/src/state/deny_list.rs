@@ -0,0 +1,78 @@
+use crate::errors::ContractError;
+use crate::state::global::Global;
+use anchor_lang::prelude::*;
+
+/// Wallet blocked from trading, exists while the block is in place.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct DenyEntry {
+    pub wallet: Pubkey,
+    pub reason: u16, // off-chain reason code
+    pub created_at: i64,
+}
+
+impl DenyEntry {
+    pub const SEED_PREFIX: &'static str = "deny";
+
+    /// Rejects a trader whose deny entry exists. `entry` is the trader's PDA, its
+    /// address checked by the caller, and has to be passed while the list is enabled.
+    pub fn require_allowed(global: &Global, entry: Option<&AccountInfo>) -> Result<()> {
+        if !global.deny_list_enabled {
+            return Ok(());
+        }
+        let entry = entry.ok_or(ContractError::DenyEntryMissing)?;
+        if *entry.owner == crate::ID && !entry.data_is_empty() {
+            msg!("wallet denied by {}", entry.key);
+            return Err(ContractError::WalletDenied.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_allowed() {
+        let key = Pubkey::new_unique();
+        let (mut empty_lamports, mut denied_lamports) = (0, 1);
+        let (mut empty_data, mut denied_data) = (vec![], vec![0; 8 + DenyEntry::INIT_SPACE]);
+        let system = anchor_lang::system_program::ID;
+        let empty = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut empty_lamports,
+            &mut empty_data,
+            &system,
+            false,
+            0,
+        );
+        let denied = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut denied_lamports,
+            &mut denied_data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        let mut global = Global::default();
+        assert!(DenyEntry::require_allowed(&global, None).is_ok());
+        assert!(DenyEntry::require_allowed(&global, Some(&denied)).is_ok());
+
+        global.deny_list_enabled = true;
+        assert_eq!(
+            DenyEntry::require_allowed(&global, None).unwrap_err(),
+            ContractError::DenyEntryMissing.into()
+        );
+        assert!(DenyEntry::require_allowed(&global, Some(&empty)).is_ok());
+        assert_eq!(
+            DenyEntry::require_allowed(&global, Some(&denied)).unwrap_err(),
+            ContractError::WalletDenied.into()
+        );
+    }
+}
@@ -1,27 +1,110 @@
-use crate::events::{GlobalUpdateEvent, IntoEvent};
+use crate::errors::ContractError;
+use crate::events::{GlobalUpdateEvent, IntoEvent, EVENT_SCHEMA_VERSION};
+use crate::merkle;
+use crate::state::launch_config::{CurveConfig, FeeSchedule, LaunchTerms, MigrationConfig};
+use crate::state::versioned::{versioned_account, Versioned, CURRENT_VERSION, RESERVED_WORDS};
+use crate::util::{bps_mul, BASIS_POINTS_DIVISOR};
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct GlobalAuthorityInput {
-    pub global_authority: Option<Pubkey>,
-    pub migration_authority: Option<Pubkey>,
+/// Admin roles, each held by a single key on `Global`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq, Eq)]
+pub enum Role {
+    Pauser,           // pause
+    FeeManager,       // set_fees
+    WhitelistManager, // add_wl, remove_wl, deny list
+    Migrator,         // create_pool, lock_pool, migrate
+    Treasurer,        // sweep_curve_remainder
+}
+
+impl Role {
+    pub const ALL: [Role; 5] = [
+        Role::Pauser,
+        Role::FeeManager,
+        Role::WhitelistManager,
+        Role::Migrator,
+        Role::Treasurer,
+    ];
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq)]
 pub enum ProgramStatus {
     Running,
-    SwapOnly,
-    SwapOnlyNoLaunch,
+    SwapOnly,         // trading and launches, no migration or withdrawals
+    SwapOnlyNoLaunch, // trading only
     Paused,
 }
 
-#[account]
-#[derive(InitSpace, Debug)]
+/// Instruction groups gated by `ProgramStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgramAction {
+    Swap,        // swap
+    CreateCurve, // create_bonding_curve
+    Migrate,     // create_pool, lock_pool, migrate
+    Withdraw,    // sweep_curve_remainder, claim_lp_fees
+    Whitelist,   // add_wl, remove_wl
+}
+
+impl ProgramStatus {
+    pub const ALL: [ProgramStatus; 4] = [
+        ProgramStatus::Running,
+        ProgramStatus::SwapOnly,
+        ProgramStatus::SwapOnlyNoLaunch,
+        ProgramStatus::Paused,
+    ];
+
+    pub fn allows(&self, action: ProgramAction) -> bool {
+        self.require(action).is_ok()
+    }
+
+    pub fn require(&self, action: ProgramAction) -> Result<()> {
+        use ProgramAction::*;
+        use ProgramStatus::*;
+        match (self, action) {
+            (Paused, _) => Err(ContractError::ProgramPaused.into()),
+            (Running, _) | (_, Swap) | (_, Whitelist) => Ok(()),
+            (SwapOnly, CreateCurve) => Ok(()),
+            (SwapOnlyNoLaunch, CreateCurve) => Err(ContractError::LaunchDisabled.into()),
+            (_, Migrate) => Err(ContractError::MigrationDisabled.into()),
+            (_, Withdraw) => Err(ContractError::WithdrawDisabled.into()),
+        }
+    }
+}
+
+/// What happens to the pool LP once a curve has migrated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq)]
+pub enum LiquidityPolicy {
+    Lock,
+    Burn,
+    Split { lock_bps: u64 }, // lock_bps locked, the rest burned
+}
+
+impl LiquidityPolicy {
+    /// Splits the LP balance into (lock, burn) amounts.
+    pub fn split(&self, lp_amount: u64) -> Option<(u64, u64)> {
+        let lock_amount = match self {
+            LiquidityPolicy::Lock => lp_amount,
+            LiquidityPolicy::Burn => 0,
+            LiquidityPolicy::Split { lock_bps } => {
+                bps_mul(*lock_bps, lp_amount, BASIS_POINTS_DIVISOR)?
+            }
+        };
+        Some((lock_amount, lp_amount.checked_sub(lock_amount)?))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            LiquidityPolicy::Split { lock_bps } => *lock_bps <= BASIS_POINTS_DIVISOR,
+            _ => true,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
 pub struct Global {
     pub status: ProgramStatus,
     pub initialized: bool,
-    pub global_authority: Pubkey,    // can update settings
-    pub migration_authority: Pubkey, // can migrate
+    pub global_authority: Pubkey, // can update settings and grant roles
+    pub migration_authority: Pubkey, // Migrator role
     pub migrate_fee_amount: u64,
     pub fee_receiver: Pubkey,
     pub initial_virtual_token_reserves: u64,
@@ -31,6 +114,95 @@ pub struct Global {
     pub mint_decimals: u8,
     pub meteora_config: Pubkey,
     pub whitelist_enabled: bool,
+    pub permissionless_migration: bool, // anyone can `migrate` a complete curve, never the split steps
+    pub keeper_reward_amount: u64,      // paid to the migration payer out of the migrate fee
+    pub migration_reserve_lamports: u64, // left in the curve at migration, swept afterwards
+    pub remainder_receiver: Pubkey,     // destination of swept curve lamports
+    pub lp_fee_creator_bps: u64,        // share of claimed LP fees paid to the curve creator
+    pub liquidity_policy: LiquidityPolicy,
+    pub max_price_deviation_bps: u64, // pool opening price vs final curve price
+    pub token_treasury: Pubkey,       // receives excess migration tokens, burned when default
+    pub pending_authority: Pubkey,    // proposed global authority, default when none
+    pub params_delay: i64,            // seconds a queued set_params waits, 0 applies at once
+    pub pending_params: Option<GlobalSettingsInput>,
+    pub pending_params_eta: i64, // earliest execute_params timestamp
+    pub pauser: Pubkey,
+    pub fee_manager: Pubkey,
+    pub whitelist_manager: Pubkey,
+    pub treasurer: Pubkey,
+    pub version: u8,
+    // carved out of the reserved tail
+    pub creator_merkle_root: [u8; 32], // whitelisted creators besides the Whitelist PDAs, zero when unused
+    pub deny_list_enabled: bool,       // swap requires the trader's DenyEntry PDA to be absent
+    pub reserved: [u8; GLOBAL_RESERVED_BYTES],
+}
+
+/// What is left of the reserved tail after the fields carved out of it.
+pub const GLOBAL_RESERVED_BYTES: usize = 8 * RESERVED_WORDS - 32 - 1;
+
+versioned_account!(Global, [167, 232, 232, 177, 200, 108, 114, 127]);
+
+/// Global as written before the version field existed.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct GlobalV1 {
+    pub status: ProgramStatus,
+    pub initialized: bool,
+    pub global_authority: Pubkey,
+    pub migration_authority: Pubkey,
+    pub migrate_fee_amount: u64,
+    pub fee_receiver: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub mint_decimals: u8,
+    pub meteora_config: Pubkey,
+    pub whitelist_enabled: bool,
+}
+
+impl Versioned for Global {
+    const V1_SPACE: usize = 1 + 1 + 32 + 32 + 8 + 32 + 8 * 4 + 1 + 32 + 1;
+    type V1 = GlobalV1;
+
+    /// Fields added since v1 take their defaults, new roles go to the global authority.
+    fn from_v1(v1: GlobalV1) -> Self {
+        let mut global = Global {
+            status: v1.status,
+            initialized: v1.initialized,
+            global_authority: v1.global_authority,
+            migration_authority: v1.migration_authority,
+            migrate_fee_amount: v1.migrate_fee_amount,
+            fee_receiver: v1.fee_receiver,
+            initial_virtual_token_reserves: v1.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: v1.initial_virtual_sol_reserves,
+            initial_real_token_reserves: v1.initial_real_token_reserves,
+            token_total_supply: v1.token_total_supply,
+            mint_decimals: v1.mint_decimals,
+            meteora_config: v1.meteora_config,
+            whitelist_enabled: v1.whitelist_enabled,
+            ..Default::default()
+        };
+        for role in [
+            Role::Pauser,
+            Role::FeeManager,
+            Role::WhitelistManager,
+            Role::Treasurer,
+        ] {
+            global.set_role(role, v1.global_authority);
+        }
+        global
+    }
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// A settings change left waiting for its timelock, see `Global::request_settings`.
+pub struct QueuedSettings {
+    pub params: GlobalSettingsInput,
+    pub eta: i64,
+    pub replaced: Option<GlobalSettingsInput>,
 }
 
 impl Default for Global {
@@ -50,11 +222,31 @@ impl Default for Global {
             migrate_fee_amount: 500,
             whitelist_enabled: true,
             meteora_config: Pubkey::default(),
+            permissionless_migration: false,
+            keeper_reward_amount: 0,
+            migration_reserve_lamports: 20_000_000,
+            remainder_receiver: Pubkey::default(),
+            lp_fee_creator_bps: 5_000,
+            liquidity_policy: LiquidityPolicy::Lock,
+            max_price_deviation_bps: 100,
+            token_treasury: Pubkey::default(),
+            pending_authority: Pubkey::default(),
+            params_delay: 0,
+            pending_params: None,
+            pending_params_eta: 0,
+            pauser: Pubkey::default(),
+            fee_manager: Pubkey::default(),
+            whitelist_manager: Pubkey::default(),
+            treasurer: Pubkey::default(),
+            version: CURRENT_VERSION,
+            creator_merkle_root: [0; 32],
+            deny_list_enabled: false,
+            reserved: [0; GLOBAL_RESERVED_BYTES],
         }
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Default, PartialEq)]
 pub struct GlobalSettingsInput {
     pub initial_virtual_token_reserves: Option<u64>,
     pub initial_virtual_sol_reserves: Option<u64>,
@@ -66,6 +258,15 @@ pub struct GlobalSettingsInput {
     pub status: Option<ProgramStatus>,
     pub whitelist_enabled: Option<bool>,
     pub meteora_config: Option<Pubkey>,
+    pub permissionless_migration: Option<bool>,
+    pub keeper_reward_amount: Option<u64>,
+    pub migration_reserve_lamports: Option<u64>,
+    pub remainder_receiver: Option<Pubkey>,
+    pub lp_fee_creator_bps: Option<u64>,
+    pub liquidity_policy: Option<LiquidityPolicy>,
+    pub max_price_deviation_bps: Option<u64>,
+    pub token_treasury: Option<Pubkey>,
+    pub params_delay: Option<i64>,
 }
 
 impl Global {
@@ -77,7 +278,13 @@ impl Global {
         [prefix_bytes, bump_slice]
     }
 
-    pub fn update_settings(&mut self, params: GlobalSettingsInput) {
+    /// Applies `params` and checks the resulting settings, see `validate_settings`.
+    pub fn update_settings(&mut self, params: GlobalSettingsInput) -> Result<()> {
+        self.apply_settings(params);
+        self.validate_settings()
+    }
+
+    fn apply_settings(&mut self, params: GlobalSettingsInput) {
         if let Some(mint_decimals) = params.mint_decimals {
             self.mint_decimals = mint_decimals;
         }
@@ -108,21 +315,249 @@ impl Global {
         if let Some(meteora_config) = params.meteora_config {
             self.meteora_config = meteora_config;
         }
+        if let Some(permissionless_migration) = params.permissionless_migration {
+            self.permissionless_migration = permissionless_migration;
+        }
+        if let Some(keeper_reward_amount) = params.keeper_reward_amount {
+            self.keeper_reward_amount = keeper_reward_amount;
+        }
+        if let Some(migration_reserve_lamports) = params.migration_reserve_lamports {
+            self.migration_reserve_lamports = migration_reserve_lamports;
+        }
+        if let Some(remainder_receiver) = params.remainder_receiver {
+            self.remainder_receiver = remainder_receiver;
+        }
+        if let Some(lp_fee_creator_bps) = params.lp_fee_creator_bps {
+            self.lp_fee_creator_bps = lp_fee_creator_bps;
+        }
+        if let Some(liquidity_policy) = params.liquidity_policy {
+            self.liquidity_policy = liquidity_policy;
+        }
+        if let Some(max_price_deviation_bps) = params.max_price_deviation_bps {
+            self.max_price_deviation_bps = max_price_deviation_bps;
+        }
+        if let Some(token_treasury) = params.token_treasury {
+            self.token_treasury = token_treasury;
+        }
+        if let Some(params_delay) = params.params_delay {
+            self.params_delay = params_delay;
+        }
+    }
+
+    /// Applies `params` subject to the timelock. Pausing is always instant, everything
+    /// else is queued for `params_delay` seconds, replacing any change already pending.
+    pub fn request_settings(
+        &mut self,
+        mut params: GlobalSettingsInput,
+        now: i64,
+    ) -> Result<Option<QueuedSettings>> {
+        if params.status == Some(ProgramStatus::Paused) {
+            self.status = ProgramStatus::Paused;
+            params.status = None;
+        }
+        if self.params_delay == 0 {
+            self.update_settings(params)?;
+            return Ok(None);
+        }
+        if params == GlobalSettingsInput::default() {
+            return Ok(None);
+        }
+
+        // reject now what execute_params would reject later
+        let mut preview = self.clone();
+        preview.update_settings(params.clone())?;
+
+        let eta = now
+            .checked_add(self.params_delay)
+            .ok_or(ContractError::ArithmeticError)?;
+        let replaced = self.pending_params.replace(params.clone());
+        self.pending_params_eta = eta;
+        Ok(Some(QueuedSettings {
+            params,
+            eta,
+            replaced,
+        }))
+    }
+
+    pub fn execute_settings(&mut self, now: i64) -> Result<GlobalSettingsInput> {
+        let params = self
+            .pending_params
+            .clone()
+            .ok_or(ContractError::NoPendingParams)?;
+        require_gte!(
+            now,
+            self.pending_params_eta,
+            ContractError::TimelockNotElapsed
+        );
+        self.pending_params = None;
+        self.update_settings(params.clone())?;
+        Ok(params)
+    }
+
+    pub fn cancel_settings(&mut self) -> Result<GlobalSettingsInput> {
+        self.pending_params
+            .take()
+            .ok_or(ContractError::NoPendingParams.into())
+    }
+
+    /// Cross-field checks, every curve created from these settings has to be able
+    /// to sell out and migrate.
+    pub fn validate_settings(&self) -> Result<()> {
+        self.curve_config().validate()?;
+        require_keys_neq!(
+            self.fee_receiver,
+            Pubkey::default(),
+            ContractError::FeeReceiverNotSet
+        );
+        require_gte!(
+            self.migrate_fee_amount,
+            self.keeper_reward_amount,
+            ContractError::KeeperRewardExceedsFee
+        );
+        require_gte!(
+            BASIS_POINTS_DIVISOR,
+            self.lp_fee_creator_bps,
+            ContractError::InvalidBps
+        );
+        require_gte!(
+            BASIS_POINTS_DIVISOR,
+            self.max_price_deviation_bps,
+            ContractError::InvalidBps
+        );
+        require!(
+            self.liquidity_policy.is_valid(),
+            ContractError::InvalidLiquidityPolicy
+        );
+        require_gte!(self.params_delay, 0, ContractError::NegativeParamsDelay);
+        Ok(())
+    }
+
+    /// Launch settings of curves created without a `LaunchConfig`.
+    pub fn curve_config(&self) -> CurveConfig {
+        CurveConfig {
+            initial_virtual_token_reserves: self.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            initial_real_token_reserves: self.initial_real_token_reserves,
+            token_total_supply: self.token_total_supply,
+            mint_decimals: self.mint_decimals,
+        }
+    }
+
+    pub fn migration_config(&self) -> MigrationConfig {
+        MigrationConfig {
+            meteora_config: self.meteora_config,
+            migrate_fee_amount: self.migrate_fee_amount,
+        }
+    }
+
+    pub fn launch_terms(&self) -> LaunchTerms {
+        LaunchTerms {
+            launch_config: Pubkey::default(),
+            curve: self.curve_config(),
+            fees: FeeSchedule::default(),
+        }
+    }
+
+    /// Merkle whitelist check, fails when no root is set.
+    pub fn creator_in_merkle_whitelist(&self, creator: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        self.creator_merkle_root != [0; 32]
+            && merkle::verify(proof, &self.creator_merkle_root, merkle::leaf(creator))
+    }
+
+    /// Whether `payer` may run the atomic `migrate`. The split `create_pool` and `lock_pool`
+    /// leave the LP with the payer in between, so they always require the Migrator role.
+    pub fn can_migrate(&self, payer: &Pubkey) -> bool {
+        self.permissionless_migration || self.has_role(Role::Migrator, payer)
     }
 
-    pub fn update_authority(&mut self, params: GlobalAuthorityInput) {
-        if let Some(global_authority) = params.global_authority {
-            self.global_authority = global_authority;
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Pauser => self.pauser,
+            Role::FeeManager => self.fee_manager,
+            Role::WhitelistManager => self.whitelist_manager,
+            Role::Migrator => self.migration_authority,
+            Role::Treasurer => self.treasurer,
         }
-        if let Some(migration_authority) = params.migration_authority {
-            self.migration_authority = migration_authority;
+    }
+
+    /// Assigns `role` to `holder`, a default key revokes it. Returns the previous holder.
+    pub fn set_role(&mut self, role: Role, holder: Pubkey) -> Pubkey {
+        let slot = match role {
+            Role::Pauser => &mut self.pauser,
+            Role::FeeManager => &mut self.fee_manager,
+            Role::WhitelistManager => &mut self.whitelist_manager,
+            Role::Migrator => &mut self.migration_authority,
+            Role::Treasurer => &mut self.treasurer,
+        };
+        std::mem::replace(slot, holder)
+    }
+
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        let holder = self.role_holder(role);
+        holder != Pubkey::default() && holder == *key
+    }
+
+    pub fn require_role(&self, role: Role, key: &Pubkey) -> Result<()> {
+        if !self.has_role(role, key) {
+            msg!("{} does not hold {:?}", key, role);
+            return Err(ContractError::MissingRole.into());
         }
+        Ok(())
+    }
+
+    /// Splits claimed LP fees into (protocol, creator) shares.
+    pub fn split_lp_fees(&self, amount: u64) -> Option<(u64, u64)> {
+        let creator = bps_mul(self.lp_fee_creator_bps, amount, BASIS_POINTS_DIVISOR)?;
+        Some((amount.checked_sub(creator)?, creator))
+    }
+
+    /// First step of an authority transfer, the new key has to accept before it takes over.
+    pub fn propose_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require_keys_neq!(
+            new_authority,
+            Pubkey::default(),
+            ContractError::InvalidArgument
+        );
+        require_keys_neq!(
+            new_authority,
+            self.global_authority,
+            ContractError::InvalidArgument
+        );
+        self.pending_authority = new_authority;
+        Ok(())
+    }
+
+    pub fn accept_authority(&mut self, signer: &Pubkey) -> Result<()> {
+        require_keys_neq!(
+            self.pending_authority,
+            Pubkey::default(),
+            ContractError::NoPendingAuthority
+        );
+        require_keys_eq!(
+            *signer,
+            self.pending_authority,
+            ContractError::InvalidPendingAuthority
+        );
+        self.global_authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    /// Drops the pending transfer and returns the key that was proposed.
+    pub fn cancel_authority(&mut self) -> Result<Pubkey> {
+        require_keys_neq!(
+            self.pending_authority,
+            Pubkey::default(),
+            ContractError::NoPendingAuthority
+        );
+        Ok(std::mem::take(&mut self.pending_authority))
     }
 }
 
 impl IntoEvent<GlobalUpdateEvent> for Global {
     fn into_event(&self) -> GlobalUpdateEvent {
         GlobalUpdateEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             global_authority: self.global_authority,
             migration_authority: self.migration_authority,
             status: self.status,
@@ -134,3 +569,477 @@ impl IntoEvent<GlobalUpdateEvent> for Global {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Default settings plus the fields that have no usable default.
+    fn configured() -> Global {
+        Global {
+            fee_receiver: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_liquidity_policy_split() {
+        assert_eq!(LiquidityPolicy::Lock.split(1_000), Some((1_000, 0)));
+        assert_eq!(LiquidityPolicy::Burn.split(1_000), Some((0, 1_000)));
+        assert_eq!(
+            LiquidityPolicy::Split { lock_bps: 2_500 }.split(1_000),
+            Some((250, 750))
+        );
+        assert_eq!(
+            LiquidityPolicy::Split { lock_bps: 10_000 }.split(u64::MAX),
+            Some((u64::MAX, 0))
+        );
+
+        assert!(LiquidityPolicy::Split { lock_bps: 10_000 }.is_valid());
+        assert!(!LiquidityPolicy::Split { lock_bps: 10_001 }.is_valid());
+        assert_eq!(
+            LiquidityPolicy::Split { lock_bps: 10_001 }.split(u64::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_creator_merkle_whitelist() {
+        let creators: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let proof = merkle::proof(&creators, 4).unwrap();
+
+        // no root, no merkle whitelist, even for an empty proof
+        let mut global = configured();
+        assert!(!global.creator_in_merkle_whitelist(&creators[4], &proof));
+        assert!(!global.creator_in_merkle_whitelist(&Pubkey::new_unique(), &[]));
+
+        global.creator_merkle_root = merkle::root(&creators);
+        assert!(global.creator_in_merkle_whitelist(&creators[4], &proof));
+        assert!(!global.creator_in_merkle_whitelist(&creators[3], &proof));
+        assert!(!global.creator_in_merkle_whitelist(&Pubkey::new_unique(), &proof));
+    }
+
+    #[test]
+    fn test_authority_transfer() {
+        let admin = Pubkey::new_unique();
+        let new_admin = Pubkey::new_unique();
+        let mut global = Global {
+            global_authority: admin,
+            ..Default::default()
+        };
+
+        // nothing to accept or cancel yet
+        assert!(global.accept_authority(&new_admin).is_err());
+        assert!(global.cancel_authority().is_err());
+        assert!(global.propose_authority(Pubkey::default()).is_err());
+        assert!(global.propose_authority(admin).is_err());
+
+        global.propose_authority(new_admin).unwrap();
+        assert_eq!(global.global_authority, admin);
+        assert_eq!(global.pending_authority, new_admin);
+
+        // only the proposed key can accept
+        assert!(global.accept_authority(&admin).is_err());
+        assert_eq!(global.cancel_authority().unwrap(), new_admin);
+        assert!(global.accept_authority(&new_admin).is_err());
+
+        global.propose_authority(new_admin).unwrap();
+        global.accept_authority(&new_admin).unwrap();
+        assert_eq!(global.global_authority, new_admin);
+        assert_eq!(global.pending_authority, Pubkey::default());
+        assert!(global.accept_authority(&new_admin).is_err());
+    }
+
+    #[test]
+    fn test_params_timelock() {
+        let mut global = Global {
+            params_delay: 3_600,
+            ..configured()
+        };
+        let fee = GlobalSettingsInput {
+            migrate_fee_amount: Some(1_000),
+            ..Default::default()
+        };
+
+        let change = global.request_settings(fee.clone(), 100).unwrap();
+        assert!(matches!(
+            change,
+            Some(QueuedSettings {
+                eta: 3_700,
+                replaced: None,
+                ..
+            })
+        ));
+        assert_eq!(global.migrate_fee_amount, 500);
+
+        assert!(global.execute_settings(3_699).is_err());
+        assert_eq!(global.execute_settings(3_700).unwrap(), fee);
+        assert_eq!(global.migrate_fee_amount, 1_000);
+        assert!(global.execute_settings(3_700).is_err());
+
+        // a new request replaces the pending one, cancel drops it
+        global.request_settings(fee.clone(), 0).unwrap();
+        let reserve = GlobalSettingsInput {
+            migration_reserve_lamports: Some(0),
+            ..Default::default()
+        };
+        let change = global.request_settings(reserve.clone(), 0).unwrap();
+        assert!(matches!(change, Some(QueuedSettings { replaced: Some(ref p), .. }) if *p == fee));
+        assert_eq!(global.cancel_settings().unwrap(), reserve);
+        assert!(global.cancel_settings().is_err());
+        assert!(global.execute_settings(i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_params_timelock_pause_is_instant() {
+        let mut global = Global {
+            params_delay: 3_600,
+            ..configured()
+        };
+        let change = global
+            .request_settings(
+                GlobalSettingsInput {
+                    status: Some(ProgramStatus::Paused),
+                    ..Default::default()
+                },
+                0,
+            )
+            .unwrap();
+        assert!(change.is_none());
+        assert_eq!(global.status, ProgramStatus::Paused);
+        assert!(global.pending_params.is_none());
+
+        // resuming is not an emergency
+        global
+            .request_settings(
+                GlobalSettingsInput {
+                    status: Some(ProgramStatus::Running),
+                    ..Default::default()
+                },
+                0,
+            )
+            .unwrap();
+        assert_eq!(global.status, ProgramStatus::Paused);
+        global.execute_settings(3_600).unwrap();
+        assert_eq!(global.status, ProgramStatus::Running);
+    }
+
+    #[test]
+    fn test_params_timelock_validation() {
+        let mut global = configured();
+        // no delay applies at once
+        let change = global
+            .request_settings(
+                GlobalSettingsInput {
+                    params_delay: Some(60),
+                    ..Default::default()
+                },
+                0,
+            )
+            .unwrap();
+        assert!(change.is_none());
+        assert_eq!(global.params_delay, 60);
+
+        // invalid settings are rejected when queued, not when executed
+        let invalid = GlobalSettingsInput {
+            keeper_reward_amount: Some(global.migrate_fee_amount + 1),
+            ..Default::default()
+        };
+        assert!(global.request_settings(invalid, 0).is_err());
+        assert!(global.pending_params.is_none());
+        assert!(global
+            .request_settings(
+                GlobalSettingsInput {
+                    params_delay: Some(-1),
+                    ..Default::default()
+                },
+                0
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_roles_reject_wrong_holder() {
+        let mut global = Global {
+            global_authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let holders: Vec<Pubkey> = Role::ALL.iter().map(|_| Pubkey::new_unique()).collect();
+        for (role, holder) in Role::ALL.iter().zip(&holders) {
+            assert_eq!(global.set_role(*role, *holder), Pubkey::default());
+        }
+
+        for (role, holder) in Role::ALL.iter().zip(&holders) {
+            assert!(global.require_role(*role, holder).is_ok());
+            for (other, other_holder) in Role::ALL.iter().zip(&holders) {
+                if other != role {
+                    assert_eq!(
+                        global.require_role(*role, other_holder).unwrap_err(),
+                        ContractError::MissingRole.into()
+                    );
+                }
+            }
+            // the global authority holds no role implicitly
+            assert_eq!(
+                global
+                    .require_role(*role, &global.global_authority)
+                    .unwrap_err(),
+                ContractError::MissingRole.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_revoked_role() {
+        let mut global = Global::default();
+        let pauser = Pubkey::new_unique();
+        global.set_role(Role::Pauser, pauser);
+        assert_eq!(global.set_role(Role::Pauser, Pubkey::default()), pauser);
+        assert!(!global.has_role(Role::Pauser, &pauser));
+        // nobody matches an unassigned role
+        assert!(!global.has_role(Role::Pauser, &Pubkey::default()));
+    }
+
+    #[test]
+    fn test_can_migrate_uses_migrator() {
+        let migrator = Pubkey::new_unique();
+        let mut global = Global {
+            global_authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        global.set_role(Role::Migrator, migrator);
+        assert_eq!(global.migration_authority, migrator);
+
+        assert!(global.can_migrate(&migrator));
+        assert!(!global.can_migrate(&global.global_authority));
+        global.permissionless_migration = true;
+        let keeper = Pubkey::new_unique();
+        assert!(global.can_migrate(&keeper));
+        // the split create_pool/lock_pool steps stay with the migrator
+        assert!(!global.has_role(Role::Migrator, &keeper));
+    }
+
+    #[test]
+    fn test_program_status_matrix() {
+        use ProgramAction::*;
+        use ProgramStatus::*;
+        let ok = || -> Result<()> { Ok(()) };
+        let paused = || -> Result<()> { Err(ContractError::ProgramPaused.into()) };
+        let no_launch = || -> Result<()> { Err(ContractError::LaunchDisabled.into()) };
+        let no_migrate = || -> Result<()> { Err(ContractError::MigrationDisabled.into()) };
+        let no_withdraw = || -> Result<()> { Err(ContractError::WithdrawDisabled.into()) };
+
+        #[rustfmt::skip]
+        let table = [
+            // status,        Swap,   CreateCurve,  Migrate,      Withdraw,      Whitelist
+            (Running,          [ok(), ok(),         ok(),         ok(),          ok()]),
+            (SwapOnly,         [ok(), ok(),         no_migrate(), no_withdraw(), ok()]),
+            (SwapOnlyNoLaunch, [ok(), no_launch(),  no_migrate(), no_withdraw(), ok()]),
+            (Paused,           [paused(), paused(), paused(),     paused(),      paused()]),
+        ];
+        assert_eq!(table.len(), ProgramStatus::ALL.len());
+
+        for (status, expected) in table {
+            for (action, expected) in [Swap, CreateCurve, Migrate, Withdraw, Whitelist]
+                .into_iter()
+                .zip(expected)
+            {
+                assert_eq!(
+                    status.require(action),
+                    expected,
+                    "{:?} / {:?}",
+                    status,
+                    action
+                );
+                assert_eq!(status.allows(action), expected.is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_settings_validation_errors() {
+        assert!(configured().validate_settings().is_ok());
+        let cases = [
+            (
+                GlobalSettingsInput {
+                    mint_decimals: Some(0),
+                    ..Default::default()
+                },
+                ContractError::InvalidMintDecimals,
+            ),
+            (
+                GlobalSettingsInput {
+                    initial_real_token_reserves: Some(0),
+                    ..Default::default()
+                },
+                ContractError::ZeroRealTokenReserves,
+            ),
+            (
+                GlobalSettingsInput {
+                    initial_real_token_reserves: Some(1_000),
+                    initial_virtual_token_reserves: Some(2_000),
+                    token_total_supply: Some(999),
+                    ..Default::default()
+                },
+                ContractError::RealReservesExceedSupply,
+            ),
+            (
+                GlobalSettingsInput {
+                    initial_virtual_token_reserves: Some(793100000000000),
+                    ..Default::default()
+                },
+                ContractError::VirtualReservesBelowReal,
+            ),
+            (
+                GlobalSettingsInput {
+                    initial_virtual_sol_reserves: Some(0),
+                    ..Default::default()
+                },
+                ContractError::ZeroVirtualSolReserves,
+            ),
+            (
+                GlobalSettingsInput {
+                    fee_receiver: Some(Pubkey::default()),
+                    ..Default::default()
+                },
+                ContractError::FeeReceiverNotSet,
+            ),
+            (
+                GlobalSettingsInput {
+                    keeper_reward_amount: Some(501),
+                    ..Default::default()
+                },
+                ContractError::KeeperRewardExceedsFee,
+            ),
+            (
+                GlobalSettingsInput {
+                    lp_fee_creator_bps: Some(10_001),
+                    ..Default::default()
+                },
+                ContractError::InvalidBps,
+            ),
+            (
+                GlobalSettingsInput {
+                    max_price_deviation_bps: Some(10_001),
+                    ..Default::default()
+                },
+                ContractError::InvalidBps,
+            ),
+            (
+                GlobalSettingsInput {
+                    liquidity_policy: Some(LiquidityPolicy::Split { lock_bps: 10_001 }),
+                    ..Default::default()
+                },
+                ContractError::InvalidLiquidityPolicy,
+            ),
+            (
+                GlobalSettingsInput {
+                    params_delay: Some(-1),
+                    ..Default::default()
+                },
+                ContractError::NegativeParamsDelay,
+            ),
+        ];
+        for (params, error) in cases {
+            let mut global = configured();
+            assert_eq!(
+                global.update_settings(params.clone()).unwrap_err(),
+                error.into(),
+                "{:?}",
+                params
+            );
+        }
+    }
+
+    use crate::state::bonding_curve::{BondingCurve, CreateBondingCurveParams};
+    use proptest::prelude::*;
+
+    prop_compose! {
+        fn settings_input()(
+            reserves in (
+                proptest::option::of(0..u64::MAX),
+                proptest::option::of(0..u64::MAX),
+                proptest::option::of(0..u64::MAX),
+                proptest::option::of(0..u64::MAX),
+            ),
+            mint_decimals in proptest::option::of(0..=18u8),
+            fees in (
+                proptest::option::of(0..10_000u64),
+                proptest::option::of(0..10_000u64),
+                proptest::option::of(0..=20_000u64),
+                proptest::option::of(0..=20_000u64),
+            ),
+            unset_fee_receiver in proptest::bool::weighted(0.1),
+            params_delay in proptest::option::of(-10..1_000_000i64),
+        ) -> GlobalSettingsInput {
+            GlobalSettingsInput {
+                initial_virtual_token_reserves: reserves.0,
+                initial_virtual_sol_reserves: reserves.1,
+                initial_real_token_reserves: reserves.2,
+                token_total_supply: reserves.3,
+                mint_decimals,
+                migrate_fee_amount: fees.0,
+                keeper_reward_amount: fees.1,
+                lp_fee_creator_bps: fees.2,
+                max_price_deviation_bps: fees.3,
+                fee_receiver: unset_fee_receiver.then_some(Pubkey::default()),
+                params_delay,
+                ..Default::default()
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(5000))]
+
+        #[test]
+        fn fuzz_test_update_settings(params in settings_input()) {
+            let mut global = configured();
+            match global.update_settings(params.clone()) {
+                Ok(()) => {
+                    prop_assert!(global.initial_real_token_reserves > 0);
+                    prop_assert!(global.initial_real_token_reserves <= global.token_total_supply);
+                    prop_assert!(global.initial_virtual_token_reserves > global.initial_real_token_reserves);
+                    prop_assert!(global.initial_virtual_sol_reserves > 0);
+                    prop_assert!(global.fee_receiver != Pubkey::default());
+                    prop_assert!(global.keeper_reward_amount <= global.migrate_fee_amount);
+                    prop_assert!(global.params_delay >= 0);
+
+                    // a curve launched with these settings sells out without underflow
+                    let curve_params = CreateBondingCurveParams {
+                        name: "test".to_string(),
+                        symbol: "test".to_string(),
+                        uri: "test".to_string(),
+                        start_time: None,
+                        launch_config: None,
+                        whitelist_proof: None,
+                    };
+                    let mut bc = BondingCurve::default();
+                    let curve = bc.update_from_params(
+                        Pubkey::default(),
+                        Pubkey::default(),
+                        &global.launch_terms(),
+                        &curve_params,
+                        &Clock::default(),
+                        0,
+                    );
+                    let real_token_reserves = curve.real_token_reserves;
+                    if let Some(result) = curve.apply_buy(u64::MAX) {
+                        prop_assert!(result.token_amount <= real_token_reserves);
+                        prop_assert!(result.completes);
+                        prop_assert!(curve.virtual_token_reserves > 0);
+                    }
+                }
+                Err(_) => {
+                    // the timelock rejects the same input before queueing it
+                    let mut queued = Global {
+                        params_delay: 60,
+                        ..configured()
+                    };
+                    prop_assert!(queued.request_settings(params, 0).is_err());
+                    prop_assert!(queued.pending_params.is_none());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use crate::errors::ContractError;
+use crate::state::global::Global;
+use crate::util::BASIS_POINTS_DIVISOR;
+use anchor_lang::prelude::*;
+
+pub const MAX_LAUNCH_CONFIG_NAME_LEN: usize = 32;
+
+/// Reserves and mint settings a new curve starts from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq)]
+pub struct CurveConfig {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub mint_decimals: u8,
+}
+
+/// Swap fee by curve age, in slots of 400 since `start_time`. Flat `launch_fee_bps`
+/// until `decay_start_slot`, linear from `decay_start_tenth_bps` to `decay_end_tenth_bps`
+/// up to `decay_end_slot`, then `fee_bps`. The decay is truncated to whole bps.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq)]
+pub struct FeeSchedule {
+    pub launch_fee_bps: u16,
+    pub decay_start_slot: u16,
+    pub decay_end_slot: u16,
+    pub decay_start_tenth_bps: u16,
+    pub decay_end_tenth_bps: u16,
+    pub fee_bps: u16,
+}
+
+impl Default for FeeSchedule {
+    /// The schedule every curve used before launch configs existed, the decay is
+    /// `(-8_300_000 * slot + 2_162_600_000) / 1_000_000`.
+    fn default() -> Self {
+        Self {
+            launch_fee_bps: 9_900,
+            decay_start_slot: 150,
+            decay_end_slot: 250,
+            decay_start_tenth_bps: 9_176,
+            decay_end_tenth_bps: 876,
+            fee_bps: 100,
+        }
+    }
+}
+
+impl FeeSchedule {
+    /// Read from curves created before the schedule was carved out of the reserved tail.
+    pub const UNSET: FeeSchedule = FeeSchedule {
+        launch_fee_bps: 0,
+        decay_start_slot: 0,
+        decay_end_slot: 0,
+        decay_start_tenth_bps: 0,
+        decay_end_tenth_bps: 0,
+        fee_bps: 0,
+    };
+
+    pub fn fee_bps_at(&self, slots_passed: i64) -> Result<u64> {
+        let start = self.decay_start_slot as i64;
+        let end = self.decay_end_slot as i64;
+        if slots_passed < start {
+            return Ok(self.launch_fee_bps as u64);
+        }
+        if slots_passed > end {
+            return Ok(self.fee_bps as u64);
+        }
+        if end == start {
+            return Ok(self.decay_end_tenth_bps as u64 / 10);
+        }
+        // start + (end - start) * elapsed / span, works for rising fees too
+        let elapsed = slots_passed - start;
+        let delta = (self.decay_end_tenth_bps as i64 - self.decay_start_tenth_bps as i64)
+            .checked_mul(elapsed)
+            .ok_or(ContractError::ArithmeticError)?
+            / (end - start);
+        Ok(((self.decay_start_tenth_bps as i64 + delta) / 10) as u64)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        // would be taken for a curve that predates fee schedules
+        require!(*self != Self::UNSET, ContractError::InvalidFeeSchedule);
+        // decay endpoints cannot exceed 6_553.5 bps
+        for bps in [self.launch_fee_bps, self.fee_bps] {
+            require_gte!(BASIS_POINTS_DIVISOR, bps as u64, ContractError::InvalidBps);
+        }
+        require_gte!(
+            self.decay_end_slot,
+            self.decay_start_slot,
+            ContractError::InvalidFeeSchedule
+        );
+        Ok(())
+    }
+}
+
+/// Pool a curve migrates into and the fee it pays for it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq)]
+pub struct MigrationConfig {
+    pub meteora_config: Pubkey,
+    pub migrate_fee_amount: u64,
+}
+
+/// Named launch type, e.g. "standard" or "premium", selected by index at curve creation.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct LaunchConfig {
+    pub index: u16,
+    #[max_len(MAX_LAUNCH_CONFIG_NAME_LEN)]
+    pub name: String,
+    pub enabled: bool, // disabled configs keep serving existing curves
+    pub curve: CurveConfig,
+    pub fees: FeeSchedule,
+    pub migration: MigrationConfig,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchConfigParams {
+    pub name: String,
+    pub curve: CurveConfig,
+    pub fees: FeeSchedule,
+    pub migration: MigrationConfig,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LaunchConfigInput {
+    pub name: Option<String>,
+    pub enabled: Option<bool>,
+    pub curve: Option<CurveConfig>,
+    pub fees: Option<FeeSchedule>,
+    pub migration: Option<MigrationConfig>,
+}
+
+/// What a new curve is created from, either `Global` or a `LaunchConfig`.
+#[derive(Clone, Debug)]
+pub struct LaunchTerms {
+    pub launch_config: Pubkey, // default for the Global settings
+    pub curve: CurveConfig,
+    pub fees: FeeSchedule,
+}
+
+impl LaunchConfig {
+    pub const SEED_PREFIX: &'static str = "launch-config";
+
+    pub fn init(&mut self, index: u16, params: LaunchConfigParams, bump: u8) {
+        self.index = index;
+        self.name = params.name;
+        self.enabled = true;
+        self.curve = params.curve;
+        self.fees = params.fees;
+        self.migration = params.migration;
+        self.bump = bump;
+    }
+
+    pub fn update(&mut self, input: LaunchConfigInput) {
+        if let Some(name) = input.name {
+            self.name = name;
+        }
+        if let Some(enabled) = input.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(curve) = input.curve {
+            self.curve = curve;
+        }
+        if let Some(fees) = input.fees {
+            self.fees = fees;
+        }
+        if let Some(migration) = input.migration {
+            self.migration = migration;
+        }
+    }
+
+    /// Same checks `Global::validate_settings` runs on the global launch settings.
+    pub fn validate(&self, global: &Global) -> Result<()> {
+        require_gte!(
+            MAX_LAUNCH_CONFIG_NAME_LEN,
+            self.name.len(),
+            ContractError::InvalidArgument
+        );
+        self.curve.validate()?;
+        self.fees.validate()?;
+        require_gte!(
+            self.migration.migrate_fee_amount,
+            global.keeper_reward_amount,
+            ContractError::KeeperRewardExceedsFee
+        );
+        Ok(())
+    }
+
+    pub fn launch_terms(&self, key: Pubkey) -> LaunchTerms {
+        LaunchTerms {
+            launch_config: key,
+            curve: self.curve,
+            fees: self.fees,
+        }
+    }
+}
+
+impl MigrationConfig {
+    /// Splits the migrate fee into the fee receiver's part and the keeper reward. The
+    /// reward is carved out of the fee and capped at it, so raising the global reward
+    /// never blocks curves of a cheaper launch config.
+    pub fn split_fee(&self, keeper_reward: u64) -> (u64, u64) {
+        let keeper_reward = keeper_reward.min(self.migrate_fee_amount);
+        (self.migrate_fee_amount - keeper_reward, keeper_reward)
+    }
+}
+
+impl CurveConfig {
+    /// Every curve created from these values has to be able to sell out and migrate.
+    pub fn validate(&self) -> Result<()> {
+        require_gt!(self.mint_decimals, 0, ContractError::InvalidMintDecimals);
+        require_gt!(
+            self.initial_real_token_reserves,
+            0,
+            ContractError::ZeroRealTokenReserves
+        );
+        require_gte!(
+            self.token_total_supply,
+            self.initial_real_token_reserves,
+            ContractError::RealReservesExceedSupply
+        );
+        // the curve still has a price once the real reserves are sold out
+        require_gt!(
+            self.initial_virtual_token_reserves,
+            self.initial_real_token_reserves,
+            ContractError::VirtualReservesBelowReal
+        );
+        require_gt!(
+            self.initial_virtual_sol_reserves,
+            0,
+            ContractError::ZeroVirtualSolReserves
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn premium() -> LaunchConfig {
+        LaunchConfig {
+            index: 1,
+            name: "premium".to_string(),
+            enabled: true,
+            curve: Global::default().curve_config(),
+            fees: FeeSchedule {
+                launch_fee_bps: 500,
+                decay_start_slot: 0,
+                decay_end_slot: 100,
+                decay_start_tenth_bps: 5_000,
+                decay_end_tenth_bps: 500,
+                fee_bps: 50,
+            },
+            migration: MigrationConfig {
+                meteora_config: Pubkey::new_unique(),
+                migrate_fee_amount: 1_000,
+            },
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_default_fee_schedule() {
+        let fees = FeeSchedule::default();
+        assert_eq!(fees.fee_bps_at(0).unwrap(), 9_900);
+        assert_eq!(fees.fee_bps_at(149).unwrap(), 9_900);
+        assert_eq!(fees.fee_bps_at(150).unwrap(), 917);
+        assert_eq!(fees.fee_bps_at(200).unwrap(), 502);
+        assert_eq!(fees.fee_bps_at(250).unwrap(), 87);
+        assert_eq!(fees.fee_bps_at(251).unwrap(), 100);
+        assert!(fees.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_fee_schedule_matches_legacy_decay() {
+        let fees = FeeSchedule::default();
+        for slot in 150..=250i64 {
+            let legacy = (-8_300_000 * slot + 2_162_600_000) / 1_000_000;
+            assert_eq!(
+                fees.fee_bps_at(slot).unwrap(),
+                legacy as u64,
+                "slot {}",
+                slot
+            );
+        }
+        assert_eq!(fees.fee_bps_at(153).unwrap(), 892);
+    }
+
+    #[test]
+    fn test_custom_fee_schedule() {
+        let fees = premium().fees;
+        assert_eq!(fees.fee_bps_at(0).unwrap(), 500);
+        assert_eq!(fees.fee_bps_at(50).unwrap(), 275);
+        assert_eq!(fees.fee_bps_at(100).unwrap(), 50);
+        assert_eq!(fees.fee_bps_at(10_000).unwrap(), 50);
+
+        // a rising schedule interpolates upwards
+        let rising = FeeSchedule {
+            decay_start_tenth_bps: 1_000,
+            decay_end_tenth_bps: 3_000,
+            ..fees
+        };
+        assert_eq!(rising.fee_bps_at(50).unwrap(), 200);
+
+        let instant = FeeSchedule {
+            decay_start_slot: 10,
+            decay_end_slot: 10,
+            ..fees
+        };
+        assert_eq!(
+            instant.fee_bps_at(10).unwrap(),
+            instant.decay_end_tenth_bps as u64 / 10
+        );
+    }
+
+    #[test]
+    fn test_launch_config_validation() {
+        let global = Global::default();
+        let mut config = premium();
+        assert!(config.validate(&global).is_ok());
+
+        config.fees.fee_bps = 10_001;
+        assert_eq!(
+            config.validate(&global).unwrap_err(),
+            ContractError::InvalidBps.into()
+        );
+        config.fees = FeeSchedule::UNSET;
+        assert_eq!(
+            config.validate(&global).unwrap_err(),
+            ContractError::InvalidFeeSchedule.into()
+        );
+        config.fees = FeeSchedule {
+            decay_start_slot: 200,
+            decay_end_slot: 100,
+            ..premium().fees
+        };
+        assert_eq!(
+            config.validate(&global).unwrap_err(),
+            ContractError::InvalidFeeSchedule.into()
+        );
+
+        let mut config = premium();
+        config.curve.initial_virtual_token_reserves = config.curve.initial_real_token_reserves;
+        assert_eq!(
+            config.validate(&global).unwrap_err(),
+            ContractError::VirtualReservesBelowReal.into()
+        );
+
+        let mut config = premium();
+        let keeper = Global {
+            keeper_reward_amount: 2_000,
+            ..Global::default()
+        };
+        assert_eq!(
+            config.validate(&keeper).unwrap_err(),
+            ContractError::KeeperRewardExceedsFee.into()
+        );
+
+        config.name = "x".repeat(MAX_LAUNCH_CONFIG_NAME_LEN + 1);
+        assert!(config.validate(&global).is_err());
+    }
+
+    #[test]
+    fn test_migration_fee_split() {
+        let migration = premium().migration;
+        assert_eq!(migration.split_fee(0), (1_000, 0));
+        assert_eq!(migration.split_fee(400), (600, 400));
+        assert_eq!(migration.split_fee(1_000), (0, 1_000));
+        // a reward raised after the config was created is capped, not an error
+        assert_eq!(migration.split_fee(2_000), (0, 1_000));
+    }
+
+    #[test]
+    fn test_launch_config_update() {
+        let mut config = premium();
+        let migration = config.migration;
+        config.update(LaunchConfigInput {
+            enabled: Some(false),
+            fees: Some(FeeSchedule::default()),
+            ..Default::default()
+        });
+        assert!(!config.enabled);
+        assert_eq!(config.fees, FeeSchedule::default());
+        assert_eq!(config.migration, migration);
+        assert_eq!(config.name, "premium");
+
+        let key = Pubkey::new_unique();
+        let terms = config.launch_terms(key);
+        assert_eq!(terms.launch_config, key);
+        assert_eq!(terms.curve, config.curve);
+    }
+}
@@ -1,4 +1,13 @@
+use crate::constants::{
+    LOCK_ESCROW_PREFIX, LP_MINT_PREFIX, METEORA_PROGRAM_KEY, QUOTE_MINT, VAULT_SEED,
+};
+use crate::errors::ContractError;
+use crate::state::global::Global;
+use crate::util::token_balance;
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, TokenAccount};
+use std::str::FromStr;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 struct CpiPoolArgs {
@@ -6,6 +15,12 @@ struct CpiPoolArgs {
     token_b_amount: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct CpiSwapArgs {
+    in_amount: u64,
+    minimum_out_amount: u64,
+}
+
 struct CpiLockArg {
     user_lock_amount: u64,
 }
@@ -26,12 +41,8 @@ pub fn get_pool_create_ix_data(amount_a: u64, amount_b: u64) -> Vec<u8> {
     buf
 }
 
-
 pub fn get_lock_lp_ix_data(lp_amount: u64) -> Vec<u8> {
-    let hash = get_function_hash(
-        "global",
-        "lock",
-    );
+    let hash = get_function_hash("global", "lock");
     let mut buf: Vec<u8> = vec![];
     buf.extend_from_slice(&hash);
     let args = CpiLockArg {
@@ -41,6 +52,404 @@ pub fn get_lock_lp_ix_data(lp_amount: u64) -> Vec<u8> {
     buf
 }
 
+/// Program vault PDA that owns every lock escrow and the LP it holds.
+pub fn get_escrow_owner() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED], &crate::ID)
+}
+
+pub fn get_lock_escrow_address(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let meteora_program_id = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+    Pubkey::find_program_address(
+        &[LOCK_ESCROW_PREFIX.as_bytes(), pool.as_ref(), owner.as_ref()],
+        &meteora_program_id,
+    )
+    .0
+}
+
+/// LP mint Meteora derives for `pool`.
+pub fn get_lp_mint_address(pool: &Pubkey) -> Pubkey {
+    let meteora_program_id = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+    Pubkey::find_program_address(
+        &[LP_MINT_PREFIX.as_bytes(), pool.as_ref()],
+        &meteora_program_id,
+    )
+    .0
+}
+
+pub fn get_create_lock_escrow_accounts(
+    pool: Pubkey,
+    lock_escrow: Pubkey,
+    owner: Pubkey,
+    lp_mint: Pubkey,
+    payer: Pubkey,
+    system_program: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new(lock_escrow, false),
+        AccountMeta::new_readonly(owner, false),
+        AccountMeta::new_readonly(lp_mint, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(system_program, false),
+    ]
+}
+
+/// Builds the Meteora `lock` metas. `owner` signs for its escrow, the LP moves from
+/// `source_tokens` into the escrow ATA, and `pool_vaults` is
+/// `[a_vault, b_vault, a_vault_lp, b_vault_lp, a_vault_lp_mint, b_vault_lp_mint]`.
+pub fn get_lock_accounts(
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    lock_escrow: Pubkey,
+    owner: Pubkey,
+    source_tokens: Pubkey,
+    token_program: Pubkey,
+    pool_vaults: [Pubkey; 6],
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new_readonly(lp_mint, false),
+        AccountMeta::new(lock_escrow, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new(source_tokens, false),
+        AccountMeta::new(get_associated_token_address(&lock_escrow, &lp_mint), false),
+        AccountMeta::new_readonly(token_program, false),
+    ];
+    accounts.extend(
+        pool_vaults
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false)),
+    );
+    accounts
+}
+
+pub fn get_swap_ix_data(in_amount: u64, minimum_out_amount: u64) -> Vec<u8> {
+    let hash = get_function_hash("global", "swap");
+    let mut buf: Vec<u8> = vec![];
+    buf.extend_from_slice(&hash);
+    let args = CpiSwapArgs {
+        in_amount,
+        minimum_out_amount,
+    };
+    args.serialize(&mut buf).unwrap();
+    buf
+}
+
+/// Pool accounts a routed swap takes as remaining accounts, in Meteora `swap` order.
+pub const SWAP_POOL_ACCOUNTS: usize = 12;
+pub const SWAP_POOL_INDEX: usize = 0;
+pub const SWAP_A_VAULT_INDEX: usize = 1;
+pub const SWAP_B_VAULT_INDEX: usize = 2;
+pub const SWAP_A_VAULT_LP_MINT_INDEX: usize = 5;
+pub const SWAP_B_VAULT_LP_MINT_INDEX: usize = 6;
+pub const SWAP_A_VAULT_LP_INDEX: usize = 7;
+pub const SWAP_B_VAULT_LP_INDEX: usize = 8;
+pub const SWAP_PROTOCOL_FEE_INDEX: usize = 9;
+pub const SWAP_VAULT_PROGRAM_INDEX: usize = 10;
+pub const SWAP_METEORA_PROGRAM_INDEX: usize = 11;
+
+/// Builds the Meteora `swap` metas from the routed pool accounts
+/// `[pool, a_vault, b_vault, a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint,
+/// a_vault_lp, b_vault_lp, protocol_token_fee, vault_program, meteora_program]`.
+pub fn get_swap_accounts(
+    pool_accounts: &[Pubkey],
+    user_source_token: Pubkey,
+    user_destination_token: Pubkey,
+    user: Pubkey,
+    token_program: Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(pool_accounts[SWAP_POOL_INDEX], false),
+        AccountMeta::new(user_source_token, false),
+        AccountMeta::new(user_destination_token, false),
+    ];
+    accounts.extend(
+        pool_accounts[1..=SWAP_PROTOCOL_FEE_INDEX]
+            .iter()
+            .map(|key| AccountMeta::new(*key, false)),
+    );
+    accounts.push(AccountMeta::new_readonly(user, true));
+    accounts.push(AccountMeta::new_readonly(
+        pool_accounts[SWAP_VAULT_PROGRAM_INDEX],
+        false,
+    ));
+    accounts.push(AccountMeta::new_readonly(token_program, false));
+    accounts
+}
+
+/// Splits a routed swap's remaining accounts into the user's wSOL account and the pool
+/// accounts, checked against the curve's `pool`.
+pub fn split_route_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    pool: &Pubkey,
+    user: &Pubkey,
+) -> Result<(&'a AccountInfo<'info>, &'a [AccountInfo<'info>])> {
+    require!(
+        remaining_accounts.len() > SWAP_POOL_ACCOUNTS,
+        ContractError::InvalidArgument
+    );
+    let user_quote_token = &remaining_accounts[0];
+    let pool_accounts = &remaining_accounts[1..SWAP_POOL_ACCOUNTS + 1];
+    require_keys_eq!(
+        pool_accounts[SWAP_POOL_INDEX].key(),
+        *pool,
+        ContractError::InvalidPool
+    );
+    require_keys_eq!(
+        pool_accounts[SWAP_METEORA_PROGRAM_INDEX].key(),
+        Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap(),
+        ContractError::InvalidPool
+    );
+
+    // SOL is wrapped into and unwrapped out of it with the user's signature
+    require_keys_eq!(*user_quote_token.owner, token::ID, ContractError::NotSOL);
+    let quote = TokenAccount::try_deserialize(&mut &user_quote_token.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        quote.mint,
+        Pubkey::from_str(QUOTE_MINT).unwrap(),
+        ContractError::NotSOL
+    );
+    require_keys_eq!(quote.owner, *user, ContractError::InvalidArgument);
+    Ok((user_quote_token, pool_accounts))
+}
+
+/// Output of a routed swap from the destination balance around the CPI, held to
+/// `min_out_amount` like a curve trade.
+pub fn routed_amount_out(before: u64, after: u64, min_out_amount: u64) -> Result<u64> {
+    let amount_out = after
+        .checked_sub(before)
+        .ok_or(ContractError::ArithmeticError)?;
+    require!(
+        amount_out >= min_out_amount,
+        ContractError::SlippageExceeded
+    );
+    Ok(amount_out)
+}
+
+/// Offsets into the Meteora pool and vault accounts read to price a routed swap.
+const POOL_FEES_OFFSET: usize = 330;
+const VAULT_TOTAL_AMOUNT_OFFSET: usize = 11;
+const VAULT_LOCKED_PROFIT_OFFSET: usize = 1203;
+const LOCKED_PROFIT_DEGRADATION_DENOMINATOR: u128 = 1_000_000_000_000;
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    (amount as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?
+        .try_into()
+        .ok()
+}
+
+/// Trade fee of a Meteora pool, charged on the input amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolFees {
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub protocol_trade_fee_numerator: u64,
+    pub protocol_trade_fee_denominator: u64,
+}
+
+impl PoolFees {
+    pub fn read(pool_data: &[u8]) -> Option<Self> {
+        Some(Self {
+            trade_fee_numerator: read_u64(pool_data, POOL_FEES_OFFSET)?,
+            trade_fee_denominator: read_u64(pool_data, POOL_FEES_OFFSET + 8)?,
+            protocol_trade_fee_numerator: read_u64(pool_data, POOL_FEES_OFFSET + 16)?,
+            protocol_trade_fee_denominator: read_u64(pool_data, POOL_FEES_OFFSET + 24)?,
+        })
+    }
+
+    /// `(lp_fee, protocol_fee)` the pool takes out of `amount_in`, in the input token.
+    pub fn trade_fees(&self, amount_in: u64) -> Option<(u64, u64)> {
+        let trade_fee = Self::fee(
+            amount_in,
+            self.trade_fee_numerator,
+            self.trade_fee_denominator,
+        )?;
+        let protocol_fee = Self::fee(
+            trade_fee,
+            self.protocol_trade_fee_numerator,
+            self.protocol_trade_fee_denominator,
+        )?;
+        Some((trade_fee.checked_sub(protocol_fee)?, protocol_fee))
+    }
+
+    // same rounding as the pool, a non-zero fee is at least 1
+    fn fee(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+        if numerator == 0 || amount == 0 {
+            return Some(0);
+        }
+        Some(mul_div(amount, numerator, denominator)?.max(1))
+    }
+}
+
+/// Unlocked vault funds backing `share` of the vault's `lp_supply`, the way the pool values
+/// its vault LP.
+pub fn vault_amount_by_share(
+    vault_data: &[u8],
+    share: u64,
+    lp_supply: u64,
+    now: i64,
+) -> Option<u64> {
+    if lp_supply == 0 {
+        return Some(0);
+    }
+    let total_amount = read_u64(vault_data, VAULT_TOTAL_AMOUNT_OFFSET)?;
+    let last_updated_locked_profit = read_u64(vault_data, VAULT_LOCKED_PROFIT_OFFSET)?;
+    let last_report = read_u64(vault_data, VAULT_LOCKED_PROFIT_OFFSET + 8)?;
+    let locked_profit_degradation = read_u64(vault_data, VAULT_LOCKED_PROFIT_OFFSET + 16)?;
+
+    // profit reported by the vault strategies unlocks linearly
+    let duration = (now.max(0) as u64).saturating_sub(last_report);
+    let locked_fund_ratio = (duration as u128).checked_mul(locked_profit_degradation as u128)?;
+    let locked_profit = if locked_fund_ratio > LOCKED_PROFIT_DEGRADATION_DENOMINATOR {
+        0
+    } else {
+        (last_updated_locked_profit as u128)
+            .checked_mul(LOCKED_PROFIT_DEGRADATION_DENOMINATOR - locked_fund_ratio)?
+            .checked_div(LOCKED_PROFIT_DEGRADATION_DENOMINATOR)? as u64
+    };
+    let unlocked_amount = total_amount.checked_sub(locked_profit)?;
+    mul_div(unlocked_amount, share, lp_supply)
+}
+
+/// Reserves `(sol, token)` behind the routed pool accounts, token A being wSOL.
+pub fn read_pool_reserves(pool_accounts: &[AccountInfo], now: i64) -> Result<(u64, u64)> {
+    let reserve = |vault: usize, vault_lp: usize, vault_lp_mint: usize| -> Result<u64> {
+        let share = token_balance(&pool_accounts[vault_lp])?;
+        let lp_supply =
+            Mint::try_deserialize(&mut &pool_accounts[vault_lp_mint].try_borrow_data()?[..])?
+                .supply;
+        let vault_data = pool_accounts[vault].try_borrow_data()?;
+        Ok(vault_amount_by_share(&vault_data, share, lp_supply, now)
+            .ok_or(ContractError::ArithmeticError)?)
+    };
+    Ok((
+        reserve(
+            SWAP_A_VAULT_INDEX,
+            SWAP_A_VAULT_LP_INDEX,
+            SWAP_A_VAULT_LP_MINT_INDEX,
+        )?,
+        reserve(
+            SWAP_B_VAULT_INDEX,
+            SWAP_B_VAULT_LP_INDEX,
+            SWAP_B_VAULT_LP_MINT_INDEX,
+        )?,
+    ))
+}
+
+pub fn get_claim_fee_ix_data(max_amount: u64) -> Vec<u8> {
+    let hash = get_function_hash("global", "claim_fee");
+    let mut buf: Vec<u8> = vec![];
+    buf.extend_from_slice(&hash);
+    max_amount.serialize(&mut buf).unwrap();
+    buf
+}
+
+/// Builds the Meteora `claim_fee` metas. `owner` signs for its escrow and takes the fees
+/// into `user_tokens` `[a, b]`, `source_tokens` is its LP account for `lp_mint`, and
+/// `pool_vaults` is `[a_token_vault, b_token_vault, a_vault, b_vault, a_vault_lp, b_vault_lp,
+/// a_vault_lp_mint, b_vault_lp_mint, vault_program]`.
+pub fn get_claim_fee_accounts(
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    lock_escrow: Pubkey,
+    owner: Pubkey,
+    source_tokens: Pubkey,
+    pool_vaults: [Pubkey; 9],
+    user_tokens: [Pubkey; 2],
+) -> Vec<AccountMeta> {
+    let (vault_program, pool_vaults) = pool_vaults.split_last().unwrap();
+    let mut accounts = vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new(lp_mint, false),
+        AccountMeta::new(lock_escrow, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new(source_tokens, false),
+        AccountMeta::new(get_associated_token_address(&lock_escrow, &lp_mint), false),
+        AccountMeta::new_readonly(token::ID, false),
+    ];
+    accounts.extend(
+        pool_vaults
+            .iter()
+            .chain(&user_tokens)
+            .map(|key| AccountMeta::new(*key, false)),
+    );
+    accounts.push(AccountMeta::new_readonly(*vault_program, false));
+    accounts
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LpFeeSide {
+    TokenA,
+    TokenB,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LpFeeRecipient {
+    Protocol,
+    Creator,
+}
+
+/// LP fees a `claim_fee` CPI moved into the vault, split per token and recipient.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LpFeePayouts {
+    pub protocol_token_a: u64,
+    pub protocol_token_b: u64,
+    pub creator_token_a: u64,
+    pub creator_token_b: u64,
+}
+
+impl LpFeePayouts {
+    /// Splits the growth of the vault `[token_a, token_b]` balances across the claim.
+    pub fn from_claim(global: &Global, before: [u64; 2], after: [u64; 2]) -> Option<Self> {
+        let (protocol_token_a, creator_token_a) =
+            global.split_lp_fees(after[0].checked_sub(before[0])?)?;
+        let (protocol_token_b, creator_token_b) =
+            global.split_lp_fees(after[1].checked_sub(before[1])?)?;
+        Some(Self {
+            protocol_token_a,
+            protocol_token_b,
+            creator_token_a,
+            creator_token_b,
+        })
+    }
+
+    /// Vault transfers to make, empty shares are skipped.
+    pub fn transfers(&self) -> Vec<(LpFeeSide, LpFeeRecipient, u64)> {
+        [
+            (
+                LpFeeSide::TokenA,
+                LpFeeRecipient::Protocol,
+                self.protocol_token_a,
+            ),
+            (
+                LpFeeSide::TokenA,
+                LpFeeRecipient::Creator,
+                self.creator_token_a,
+            ),
+            (
+                LpFeeSide::TokenB,
+                LpFeeRecipient::Protocol,
+                self.protocol_token_b,
+            ),
+            (
+                LpFeeSide::TokenB,
+                LpFeeRecipient::Creator,
+                self.creator_token_b,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, _, amount)| *amount > 0)
+        .collect()
+    }
+}
+
 pub fn get_function_hash(namespace: &str, name: &str) -> [u8; 8] {
     let preimage = format!("{}:{}", namespace, name);
     let mut sighash = [0u8; 8];
@@ -50,3 +459,556 @@ pub fn get_function_hash(namespace: &str, name: &str) -> [u8; 8] {
     sighash
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::bonding_curve::{
+        market_cap_from_reserves, price_from_reserves, BondingCurve,
+    };
+    use crate::state::testing::{mint_data, token_account_data, TestAccount};
+    use anchor_lang::solana_program::{instruction::Instruction, program_pack::Pack};
+    use anchor_spl::token::spl_token;
+
+    /// Stand-in for the Meteora side of `claim_fee`: checks the sighash and decodes the args.
+    fn mock_meteora_claim_fee(data: &[u8]) -> Option<u64> {
+        let (sighash, mut args) = data.split_at(8);
+        if sighash != get_function_hash("global", "claim_fee") {
+            return None;
+        }
+        u64::deserialize(&mut args).ok()
+    }
+
+    #[test]
+    fn test_claim_fee_ix_data() {
+        let data = get_claim_fee_ix_data(u64::MAX);
+        assert_eq!(data.len(), 16);
+        assert_eq!(mock_meteora_claim_fee(&data), Some(u64::MAX));
+        assert_eq!(mock_meteora_claim_fee(&get_lock_lp_ix_data(1)), None);
+    }
+
+    /// `claim_fee` accounts in Meteora IDL order, as `(name, writable, signer)`.
+    const CLAIM_FEE_IDL: [(&str, bool, bool); 18] = [
+        ("pool", true, false),
+        ("lp_mint", true, false),
+        ("lock_escrow", true, false),
+        ("owner", true, true),
+        ("source_tokens", true, false),
+        ("escrow_vault", true, false),
+        ("token_program", false, false),
+        ("a_token_vault", true, false),
+        ("b_token_vault", true, false),
+        ("a_vault", true, false),
+        ("b_vault", true, false),
+        ("a_vault_lp", true, false),
+        ("b_vault_lp", true, false),
+        ("a_vault_lp_mint", true, false),
+        ("b_vault_lp_mint", true, false),
+        ("user_a_token", true, false),
+        ("user_b_token", true, false),
+        ("vault_program", false, false),
+    ];
+
+    fn token_account(accounts: &[TestAccount], key: Pubkey) -> Option<spl_token::state::Account> {
+        let account = accounts.iter().find(|account| account.key == key)?;
+        spl_token::state::Account::unpack(&account.data).ok()
+    }
+
+    /// Local stand-in for the Meteora program. Takes `ix` the way `invoke_signed` hands it
+    /// over, checks the accounts against the `claim_fee` IDL and pays the `pending` `[a, b]`
+    /// fees into the owner's token accounts. Errors name the first account that is off.
+    fn mock_meteora_program(
+        ix: &Instruction,
+        accounts: &mut [TestAccount],
+        signer_seeds: &[&[&[u8]]],
+        pending: [u64; 2],
+    ) -> std::result::Result<(), &'static str> {
+        if ix.program_id != Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap() {
+            return Err("program_id");
+        }
+        let max_amount = mock_meteora_claim_fee(&ix.data).ok_or("data")?;
+        if ix.accounts.len() != CLAIM_FEE_IDL.len() {
+            return Err("accounts");
+        }
+        let signers: Vec<Pubkey> = signer_seeds
+            .iter()
+            .filter_map(|seeds| Pubkey::create_program_address(seeds, &crate::ID).ok())
+            .collect();
+        for (meta, (name, writable, signer)) in ix.accounts.iter().zip(CLAIM_FEE_IDL) {
+            if meta.is_writable != writable || meta.is_signer != signer {
+                return Err(name);
+            }
+            // the runtime only passes what the caller handed in, and signs for its PDAs
+            if !accounts.iter().any(|account| account.key == meta.pubkey)
+                || (signer && !signers.contains(&meta.pubkey))
+            {
+                return Err(name);
+            }
+        }
+
+        let key = |index: usize| ix.accounts[index].pubkey;
+        let (pool, lp_mint, lock_escrow, owner) = (key(0), key(1), key(2), key(3));
+        if lp_mint != get_lp_mint_address(&pool) {
+            return Err("lp_mint");
+        }
+        if lock_escrow != get_lock_escrow_address(&pool, &owner) {
+            return Err("lock_escrow");
+        }
+        match token_account(accounts, key(4)) {
+            Some(source) if source.mint == lp_mint && source.owner == owner => {}
+            _ => return Err("source_tokens"),
+        }
+        if key(5) != get_associated_token_address(&lock_escrow, &lp_mint) {
+            return Err("escrow_vault");
+        }
+        if key(6) != token::ID {
+            return Err("token_program");
+        }
+        for (index, name, amount) in [
+            (15, "user_a_token", pending[0]),
+            (16, "user_b_token", pending[1]),
+        ] {
+            let mut user_token = token_account(accounts, key(index))
+                .filter(|user_token| user_token.owner == owner)
+                .ok_or(name)?;
+            user_token.amount += amount.min(max_amount);
+            let account = accounts
+                .iter_mut()
+                .find(|account| account.key == key(index))
+                .unwrap();
+            user_token.pack_into_slice(&mut account.data);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_claim_lp_fees_payouts() {
+        let mut global = Global {
+            lp_fee_creator_bps: 2_500,
+            ..Default::default()
+        };
+        let meteora_program_id = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+        let pool = Pubkey::new_unique();
+        let lp_mint = get_lp_mint_address(&pool);
+        let (vault, vault_bump) = get_escrow_owner();
+        let lock_escrow = get_lock_escrow_address(&pool, &vault);
+        let source_tokens = get_associated_token_address(&vault, &lp_mint);
+        let escrow_vault = get_associated_token_address(&lock_escrow, &lp_mint);
+        let quote_mint = Pubkey::from_str(QUOTE_MINT).unwrap();
+        let token_mint = Pubkey::new_unique();
+        let pool_vaults = [(); 9].map(|_| Pubkey::new_unique());
+        let vault_token_a = Pubkey::new_unique();
+        let vault_token_b = Pubkey::new_unique();
+
+        // claim_lp_fees' accounts, the vault already holds some dust before the claim
+        let at = |key: Pubkey, owner: Pubkey, data: Vec<u8>| TestAccount {
+            key,
+            ..TestAccount::new(owner, data)
+        };
+        let mut accounts = vec![
+            at(pool, meteora_program_id, vec![]),
+            at(lp_mint, token::ID, mint_data(1_000)),
+            at(lock_escrow, meteora_program_id, vec![]),
+            at(vault, crate::ID, vec![]),
+            at(
+                source_tokens,
+                token::ID,
+                token_account_data(lp_mint, vault, 0),
+            ),
+            at(
+                escrow_vault,
+                token::ID,
+                token_account_data(lp_mint, lock_escrow, 1_000),
+            ),
+            TestAccount::program(token::ID),
+            at(
+                vault_token_a,
+                token::ID,
+                token_account_data(quote_mint, vault, 5),
+            ),
+            at(
+                vault_token_b,
+                token::ID,
+                token_account_data(token_mint, vault, 7),
+            ),
+        ];
+        accounts.extend(
+            pool_vaults
+                .iter()
+                .map(|key| at(*key, Pubkey::default(), vec![])),
+        );
+
+        let ix = Instruction {
+            program_id: meteora_program_id,
+            accounts: get_claim_fee_accounts(
+                pool,
+                lp_mint,
+                lock_escrow,
+                vault,
+                source_tokens,
+                pool_vaults,
+                [vault_token_a, vault_token_b],
+            ),
+            data: get_claim_fee_ix_data(u64::MAX),
+        };
+        let vault_bump = vault_bump.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, vault_bump.as_ref()]];
+        let balances = |accounts: &[TestAccount]| {
+            [vault_token_a, vault_token_b].map(|key| token_account(accounts, key).unwrap().amount)
+        };
+
+        // the layout before source_tokens was passed, and a read-only lp_mint, are refused
+        let mut without_source = ix.clone();
+        without_source.accounts.remove(4);
+        assert_eq!(
+            mock_meteora_program(&without_source, &mut accounts, signer_seeds, [0, 0]),
+            Err("accounts")
+        );
+        let mut readonly_lp_mint = ix.clone();
+        readonly_lp_mint.accounts[1].is_writable = false;
+        assert_eq!(
+            mock_meteora_program(&readonly_lp_mint, &mut accounts, signer_seeds, [0, 0]),
+            Err("lp_mint")
+        );
+        // only the vault PDA can sign for its escrow
+        assert_eq!(
+            mock_meteora_program(&ix, &mut accounts, &[], [0, 0]),
+            Err("owner")
+        );
+
+        // claim everything pending, as the handler does
+        let before = balances(&accounts);
+        mock_meteora_program(&ix, &mut accounts, signer_seeds, [1_000, 3]).unwrap();
+        let after = balances(&accounts);
+        assert_eq!(after, [1_005, 10]);
+
+        let payouts = LpFeePayouts::from_claim(&global, before, after).unwrap();
+        assert_eq!(
+            payouts,
+            LpFeePayouts {
+                protocol_token_a: 750,
+                protocol_token_b: 3,
+                creator_token_a: 250,
+                creator_token_b: 0,
+            }
+        );
+        // only the claimed amounts leave the vault
+        let transfers = payouts.transfers();
+        assert_eq!(transfers.len(), 3);
+        for (side, claimed) in [(LpFeeSide::TokenA, 1_000), (LpFeeSide::TokenB, 3)] {
+            let paid: u64 = transfers
+                .iter()
+                .filter(|(transfer_side, _, _)| *transfer_side == side)
+                .map(|(_, _, amount)| amount)
+                .sum();
+            assert_eq!(paid, claimed);
+        }
+
+        // nothing claimed, nothing moves
+        global.lp_fee_creator_bps = 10_000;
+        let payouts = LpFeePayouts::from_claim(&global, before, before).unwrap();
+        assert!(payouts.transfers().is_empty());
+        // a vault that shrank across the claim is rejected
+        assert_eq!(LpFeePayouts::from_claim(&global, after, before), None);
+    }
+
+    #[test]
+    fn test_lock_escrow_owned_by_vault() {
+        let pool = Pubkey::new_unique();
+        let lp_mint = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let (vault, _) = get_escrow_owner();
+        assert_eq!(
+            vault,
+            Pubkey::find_program_address(&[VAULT_SEED], &crate::ID).0
+        );
+
+        let lock_escrow = get_lock_escrow_address(&pool, &vault);
+        assert_ne!(lock_escrow, get_lock_escrow_address(&pool, &payer));
+
+        let metas = get_create_lock_escrow_accounts(
+            pool,
+            lock_escrow,
+            vault,
+            lp_mint,
+            payer,
+            Pubkey::default(),
+        );
+        // [pool, lock_escrow, owner, lp_mint, payer, system_program]
+        assert_eq!(metas[1].pubkey, lock_escrow);
+        assert_eq!(metas[2].pubkey, vault);
+        assert!(!metas[2].is_signer);
+        assert_eq!(metas[4].pubkey, payer);
+        assert!(metas[4].is_signer);
+    }
+
+    #[test]
+    fn test_lp_mint_address() {
+        let pool = Pubkey::new_unique();
+        let meteora_program_id = Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+        assert_eq!(
+            get_lp_mint_address(&pool),
+            Pubkey::find_program_address(&[b"lp_mint", pool.as_ref()], &meteora_program_id).0
+        );
+        // an LP mint of another pool is rejected
+        assert_ne!(
+            get_lp_mint_address(&pool),
+            get_lp_mint_address(&Pubkey::new_unique())
+        );
+    }
+
+    /// Stand-in for the Meteora side of `swap`: decodes the args and applies constant product.
+    fn mock_meteora_swap(data: &[u8], reserve_in: u64, reserve_out: u64) -> Option<u64> {
+        let (sighash, mut args) = data.split_at(8);
+        if sighash != get_function_hash("global", "swap") {
+            return None;
+        }
+        let args = CpiSwapArgs::deserialize(&mut args).ok()?;
+        let k = (reserve_in as u128).checked_mul(reserve_out as u128)?;
+        let new_reserve_in = (reserve_in as u128).checked_add(args.in_amount as u128)?;
+        let out = (reserve_out as u128).checked_sub(k.div_ceil(new_reserve_in))?;
+        let out = u64::try_from(out).ok()?;
+        (out >= args.minimum_out_amount).then_some(out)
+    }
+
+    #[test]
+    fn test_swap_ix_data() {
+        let data = get_swap_ix_data(1_000_000, 0);
+        assert_eq!(data.len(), 24);
+        let out = mock_meteora_swap(&data, 1_000_000_000, 1_000_000_000).unwrap();
+        assert_eq!(out, 999_000);
+
+        // min out is forwarded, the pool rejects the trade below it
+        assert_eq!(
+            mock_meteora_swap(
+                &get_swap_ix_data(1_000_000, out),
+                1_000_000_000,
+                1_000_000_000
+            ),
+            Some(out)
+        );
+        assert_eq!(
+            mock_meteora_swap(
+                &get_swap_ix_data(1_000_000, out + 1),
+                1_000_000_000,
+                1_000_000_000
+            ),
+            None
+        );
+        assert_eq!(mock_meteora_swap(&get_claim_fee_ix_data(1), 1, 1), None);
+    }
+
+    #[test]
+    fn test_swap_accounts_order() {
+        let pool_accounts: Vec<Pubkey> = (0..SWAP_POOL_ACCOUNTS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let quote = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+
+        // sell: tokens in, wSOL out
+        let metas = get_swap_accounts(&pool_accounts, token, quote, user, token_program);
+        // [pool, source, destination, a_vault .. protocol_token_fee, user, vault_program, token_program]
+        assert_eq!(metas.len(), 15);
+        assert_eq!(metas[0].pubkey, pool_accounts[SWAP_POOL_INDEX]);
+        assert_eq!(metas[1].pubkey, token);
+        assert_eq!(metas[2].pubkey, quote);
+        for (meta, key) in metas[3..12].iter().zip(&pool_accounts[1..10]) {
+            assert_eq!(meta.pubkey, *key);
+            assert!(meta.is_writable);
+        }
+        assert_eq!(metas[11].pubkey, pool_accounts[SWAP_PROTOCOL_FEE_INDEX]);
+        assert_eq!(metas[12].pubkey, user);
+        assert!(metas[12].is_signer);
+        assert_eq!(metas[13].pubkey, pool_accounts[SWAP_VAULT_PROGRAM_INDEX]);
+        assert_eq!(metas[14].pubkey, token_program);
+        assert!(!metas
+            .iter()
+            .any(|meta| meta.pubkey == pool_accounts[SWAP_METEORA_PROGRAM_INDEX]));
+
+        // buy: wSOL in, tokens out
+        let metas = get_swap_accounts(&pool_accounts, quote, token, user, token_program);
+        assert_eq!(metas[1].pubkey, quote);
+        assert_eq!(metas[2].pubkey, token);
+    }
+
+    fn vault_data(total_amount: u64, locked_profit: u64, last_report: u64) -> Vec<u8> {
+        let mut data = vec![0; VAULT_LOCKED_PROFIT_OFFSET + 24];
+        data[VAULT_TOTAL_AMOUNT_OFFSET..][..8].copy_from_slice(&total_amount.to_le_bytes());
+        let tracker = &mut data[VAULT_LOCKED_PROFIT_OFFSET..];
+        tracker[..8].copy_from_slice(&locked_profit.to_le_bytes());
+        tracker[8..16].copy_from_slice(&last_report.to_le_bytes());
+        // fully unlocked 100 seconds after a report
+        tracker[16..24]
+            .copy_from_slice(&(LOCKED_PROFIT_DEGRADATION_DENOMINATOR as u64 / 100).to_le_bytes());
+        data
+    }
+
+    fn pool_data(fees: [u64; 4]) -> Vec<u8> {
+        let mut data = vec![0; POOL_FEES_OFFSET + 32];
+        for (i, value) in fees.iter().enumerate() {
+            data[POOL_FEES_OFFSET + 8 * i..][..8].copy_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    /// `[user_quote_token, pool accounts..]` as a routed swap passes them.
+    fn route_accounts(user: Pubkey) -> Vec<TestAccount> {
+        let quote_mint = Pubkey::from_str(QUOTE_MINT).unwrap();
+        let mut accounts = vec![TestAccount::new(
+            token::ID,
+            token_account_data(quote_mint, user, 0),
+        )];
+        accounts
+            .extend((0..SWAP_POOL_ACCOUNTS).map(|_| TestAccount::new(Pubkey::default(), vec![])));
+        accounts[1 + SWAP_METEORA_PROGRAM_INDEX].key =
+            Pubkey::from_str(METEORA_PROGRAM_KEY).unwrap();
+        accounts
+    }
+
+    fn check_route(accounts: &mut [TestAccount], pool: &Pubkey, user: &Pubkey) -> Result<Pubkey> {
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let (quote, pool_accounts) = split_route_accounts(&infos, pool, user)?;
+        assert_eq!(pool_accounts.len(), SWAP_POOL_ACCOUNTS);
+        Ok(quote.key())
+    }
+
+    #[test]
+    fn test_split_route_accounts() {
+        let user = Pubkey::new_unique();
+        let mut accounts = route_accounts(user);
+        let pool = accounts[1 + SWAP_POOL_INDEX].key;
+        let quote = accounts[0].key;
+        assert_eq!(check_route(&mut accounts, &pool, &user).unwrap(), quote);
+
+        // another pool than the one recorded at migration
+        assert_eq!(
+            check_route(&mut accounts, &Pubkey::new_unique(), &user).unwrap_err(),
+            ContractError::InvalidPool.into()
+        );
+        // missing accounts
+        assert_eq!(
+            check_route(&mut accounts[..SWAP_POOL_ACCOUNTS], &pool, &user).unwrap_err(),
+            ContractError::InvalidArgument.into()
+        );
+        // a program standing in for Meteora
+        let mut spoofed = route_accounts(user);
+        spoofed[1 + SWAP_POOL_INDEX].key = pool;
+        spoofed[1 + SWAP_METEORA_PROGRAM_INDEX].key = Pubkey::new_unique();
+        assert_eq!(
+            check_route(&mut spoofed, &pool, &user).unwrap_err(),
+            ContractError::InvalidPool.into()
+        );
+
+        // the quote account must be the user's wSOL account
+        assert_eq!(
+            check_route(&mut accounts, &pool, &Pubkey::new_unique()).unwrap_err(),
+            ContractError::InvalidArgument.into()
+        );
+        accounts[0].data = token_account_data(Pubkey::new_unique(), user, 0);
+        assert_eq!(
+            check_route(&mut accounts, &pool, &user).unwrap_err(),
+            ContractError::NotSOL.into()
+        );
+        accounts[0].data = token_account_data(Pubkey::from_str(QUOTE_MINT).unwrap(), user, 0);
+        accounts[0].owner = Pubkey::new_unique();
+        assert_eq!(
+            check_route(&mut accounts, &pool, &user).unwrap_err(),
+            ContractError::NotSOL.into()
+        );
+    }
+
+    #[test]
+    fn test_routed_amount_out() {
+        assert_eq!(routed_amount_out(100, 150, 50).unwrap(), 50);
+        assert_eq!(routed_amount_out(100, 150, 0).unwrap(), 50);
+        assert_eq!(
+            routed_amount_out(100, 150, 51).unwrap_err(),
+            ContractError::SlippageExceeded.into()
+        );
+        assert_eq!(
+            routed_amount_out(150, 100, 0).unwrap_err(),
+            ContractError::ArithmeticError.into()
+        );
+    }
+
+    #[test]
+    fn test_pool_fees() {
+        // 0.25% trade fee, 20% of it to the protocol
+        let fees = PoolFees::read(&pool_data([25, 10_000, 20, 100])).unwrap();
+        assert_eq!(fees.trade_fees(1_000_000), Some((2_000, 500)));
+        // a non-zero fee is at least one unit
+        assert_eq!(fees.trade_fees(1), Some((0, 1)));
+        assert_eq!(fees.trade_fees(0), Some((0, 0)));
+
+        let no_protocol_fee = PoolFees::read(&pool_data([25, 10_000, 0, 100])).unwrap();
+        assert_eq!(no_protocol_fee.trade_fees(1_000_000), Some((2_500, 0)));
+        assert_eq!(PoolFees::read(&[0; POOL_FEES_OFFSET]), None);
+    }
+
+    #[test]
+    fn test_vault_amount_by_share() {
+        let data = vault_data(1_000, 100, 1_000);
+        // half the locked profit has unlocked
+        assert_eq!(vault_amount_by_share(&data, 1, 2, 1_050), Some(475));
+        assert_eq!(vault_amount_by_share(&data, 1, 2, 1_100), Some(500));
+        assert_eq!(vault_amount_by_share(&data, 1, 2, 5_000), Some(500));
+        assert_eq!(vault_amount_by_share(&data, 1, 2, 1_000), Some(450));
+        assert_eq!(vault_amount_by_share(&data, 0, 0, 1_000), Some(0));
+        assert_eq!(vault_amount_by_share(&data[..20], 1, 2, 1_000), None);
+    }
+
+    #[test]
+    fn test_read_pool_reserves() {
+        let mut accounts: Vec<TestAccount> = (0..SWAP_POOL_ACCOUNTS)
+            .map(|_| TestAccount::new(Pubkey::default(), vec![]))
+            .collect();
+        accounts[SWAP_A_VAULT_INDEX].data = vault_data(400_000_000_000, 0, 0);
+        accounts[SWAP_B_VAULT_INDEX].data = vault_data(1_000_000_000_000_000, 0, 0);
+        accounts[SWAP_A_VAULT_LP_MINT_INDEX].data = mint_data(1_000);
+        accounts[SWAP_B_VAULT_LP_MINT_INDEX].data = mint_data(1_000);
+        // the pool owns a fifth of each vault
+        let owner = Pubkey::new_unique();
+        accounts[SWAP_A_VAULT_LP_INDEX].data = token_account_data(Pubkey::new_unique(), owner, 200);
+        accounts[SWAP_B_VAULT_LP_INDEX].data = token_account_data(Pubkey::new_unique(), owner, 200);
+
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let (sol_reserves, token_reserves) = read_pool_reserves(&infos, 0).unwrap();
+        assert_eq!(sol_reserves, 80_000_000_000);
+        assert_eq!(token_reserves, 200_000_000_000_000);
+
+        // a routed trade is priced the same way as a curve with those reserves
+        let curve = BondingCurve {
+            virtual_sol_reserves: sol_reserves,
+            virtual_token_reserves: token_reserves,
+            token_total_supply: 1_000_000_000_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            price_from_reserves(sol_reserves, token_reserves),
+            curve.marginal_price()
+        );
+        assert_eq!(
+            market_cap_from_reserves(sol_reserves, token_reserves, curve.token_total_supply),
+            curve.market_cap_lamports()
+        );
+    }
+
+    #[test]
+    fn test_split_lp_fees() {
+        let mut global = Global::default();
+        assert_eq!(global.split_lp_fees(1_000), Some((500, 500)));
+
+        global.lp_fee_creator_bps = 2_500;
+        assert_eq!(global.split_lp_fees(1_000), Some((750, 250)));
+        // rounding dust stays with the protocol
+        assert_eq!(global.split_lp_fees(3), Some((3, 0)));
+
+        global.lp_fee_creator_bps = 0;
+        assert_eq!(global.split_lp_fees(u64::MAX), Some((u64::MAX, 0)));
+
+        global.lp_fee_creator_bps = 10_000;
+        assert_eq!(global.split_lp_fees(u64::MAX), Some((0, u64::MAX)));
+    }
+}
@@ -1,4 +1,10 @@
 pub mod bonding_curve;
+pub mod council;
+pub mod deny_list;
 pub mod global;
+pub mod launch_config;
 pub mod meteora;
+#[cfg(test)]
+pub mod testing;
+pub mod versioned;
 pub mod whitelist;
\ No newline at end of file
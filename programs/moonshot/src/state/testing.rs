@@ -0,0 +1,134 @@
+//! Account fixtures shared by the unit tests.
+
+use anchor_lang::__private::base64::{engine::general_purpose::STANDARD, Engine};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::SUCCESS;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_spl::token::spl_token;
+use std::cell::RefCell;
+use std::sync::Once;
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    pub fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 1,
+            data,
+            executable: false,
+        }
+    }
+
+    /// An executable account at `id`, passes as `Program<'_, T>`.
+    pub fn program(id: Pubkey) -> Self {
+        Self {
+            key: id,
+            executable: true,
+            ..Self::new(Pubkey::default(), vec![])
+        }
+    }
+
+    /// A program account holding `account` behind its discriminator.
+    pub fn program_owned<T: AccountSerialize>(account: &T) -> Self {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        Self::new(crate::ID, data)
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+pub fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+pub fn mint_data(supply: u64) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        supply,
+        decimals: 9,
+        is_initialized: true,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// What `Clock::get` returns to code run through `program_logs`.
+pub const TEST_CLOCK: Clock = Clock {
+    slot: 300,
+    epoch_start_timestamp: 0,
+    epoch: 0,
+    leader_schedule_epoch: 0,
+    unix_timestamp: 1_700_000_000,
+};
+
+thread_local! {
+    static PROGRAM_LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runtime stand-in for handler code run off-chain. CPIs already succeed without a
+/// runtime, this adds the sysvars and keeps `emit!` output the way the runtime logs it.
+struct ProgramStubs;
+
+impl SyscallStubs for ProgramStubs {
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        PROGRAM_LOGS.with(|logs| {
+            logs.borrow_mut()
+                .push(format!("Program data: {}", fields.join(" ")))
+        });
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: `Clock::get` passes a pointer to its own `Clock`
+        unsafe { var_addr.cast::<Clock>().write(TEST_CLOCK) };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: `Rent::get` passes a pointer to its own `Rent`
+        unsafe { var_addr.cast::<Rent>().write(Rent::default()) };
+        SUCCESS
+    }
+}
+
+/// Runs `f` with the sysvars available and returns the `Program data:` lines it logged.
+pub fn program_logs<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(ProgramStubs));
+    });
+    PROGRAM_LOGS.with(|logs| logs.borrow_mut().clear());
+    let result = f();
+    (result, PROGRAM_LOGS.with(|logs| logs.take()))
+}
@@ -0,0 +1,262 @@
+use crate::errors::ContractError;
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+/// Layout version written by this program. v1 accounts predate the version field.
+pub const CURRENT_VERSION: u8 = 2;
+/// Zeroed tail kept on every account so later fields need no realloc, 64 bytes.
+pub const RESERVED_WORDS: usize = 8;
+
+/// An account that can still be read from its v1 encoding.
+pub trait Versioned: AnchorSerialize + AnchorDeserialize + Discriminator + Space {
+    /// Borsh size of the v1 layout, discriminator excluded.
+    const V1_SPACE: usize;
+    type V1: AnchorDeserialize;
+
+    fn from_v1(v1: Self::V1) -> Self;
+    fn version(&self) -> u8;
+}
+
+/// Decodes account data (discriminator excluded), upgrading v1 layouts in memory.
+pub fn deserialize_versioned<T: Versioned>(mut data: &[u8]) -> Result<T> {
+    if data.len() == T::V1_SPACE {
+        let v1 = T::V1::deserialize(&mut data)
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        return Ok(T::from_v1(v1));
+    }
+    let account =
+        T::deserialize(&mut data).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+    require_gte!(
+        CURRENT_VERSION,
+        account.version(),
+        ContractError::UnsupportedAccountVersion
+    );
+    Ok(account)
+}
+
+/// Grows an account written with an older layout to the current size. The payer
+/// covers the extra rent only, lamports the account already holds stay untouched.
+/// Returns the previous data length.
+pub fn realloc_to_current<'info, T: Versioned>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<usize> {
+    let old_len = account.data_len();
+    let new_len = 8 + T::INIT_SPACE;
+    if old_len >= new_len {
+        return Ok(old_len);
+    }
+
+    let rent = Rent::get()?;
+    let rent_delta = rent
+        .minimum_balance(new_len)
+        .checked_sub(rent.minimum_balance(old_len))
+        .ok_or(ContractError::ArithmeticError)?;
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        ),
+        rent_delta,
+    )?;
+    account.realloc(new_len, true)?;
+    msg!(
+        "Account {} grown from {} to {} bytes",
+        account.key,
+        old_len,
+        new_len
+    );
+    Ok(old_len)
+}
+
+/// Account trait impls of `#[account]`, with a deserializer that accepts v1 data.
+macro_rules! versioned_account {
+    ($name:ident, $discriminator:expr) => {
+        impl anchor_lang::Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+
+        impl anchor_lang::Owner for $name {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        impl anchor_lang::AccountSerialize for $name {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                writer
+                    .write_all(<$name as anchor_lang::Discriminator>::DISCRIMINATOR)
+                    .map_err(|_| error!(ErrorCode::AccountDidNotSerialize))?;
+                AnchorSerialize::serialize(self, writer)
+                    .map_err(|_| error!(ErrorCode::AccountDidNotSerialize))
+            }
+        }
+
+        impl anchor_lang::AccountDeserialize for $name {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                let discriminator = <$name as anchor_lang::Discriminator>::DISCRIMINATOR;
+                if buf.len() < discriminator.len() {
+                    return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+                }
+                if &buf[..discriminator.len()] != discriminator {
+                    return Err(error!(ErrorCode::AccountDiscriminatorMismatch)
+                        .with_account_name(stringify!($name)));
+                }
+                Self::try_deserialize_unchecked(buf)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                let discriminator = <$name as anchor_lang::Discriminator>::DISCRIMINATOR;
+                crate::state::versioned::deserialize_versioned(&buf[discriminator.len()..])
+            }
+        }
+    };
+}
+pub(crate) use versioned_account;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::bonding_curve::{BondingCurve, BondingCurveV1, CurveStatus};
+    use crate::state::global::{Global, GlobalV1, ProgramStatus, Role};
+    use anchor_lang::solana_program::hash::hash;
+
+    fn account_bytes<T: Discriminator>(body: &impl AnchorSerialize) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        body.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn global_v1(authority: Pubkey) -> GlobalV1 {
+        GlobalV1 {
+            status: ProgramStatus::Running,
+            initialized: true,
+            global_authority: authority,
+            migration_authority: Pubkey::new_unique(),
+            migrate_fee_amount: 500,
+            fee_receiver: Pubkey::new_unique(),
+            initial_virtual_token_reserves: 1_073_000_000,
+            initial_virtual_sol_reserves: 30,
+            initial_real_token_reserves: 793_100_000,
+            token_total_supply: 1_000_000_000,
+            mint_decimals: 6,
+            meteora_config: Pubkey::new_unique(),
+            whitelist_enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_discriminators_unchanged() {
+        assert_eq!(
+            Global::DISCRIMINATOR,
+            &hash(b"account:Global").to_bytes()[..8]
+        );
+        assert_eq!(
+            BondingCurve::DISCRIMINATOR,
+            &hash(b"account:BondingCurve").to_bytes()[..8]
+        );
+    }
+
+    #[test]
+    fn test_load_v1_global() {
+        let authority = Pubkey::new_unique();
+        let v1 = global_v1(authority);
+        let data = account_bytes::<Global>(&v1);
+        assert_eq!(data.len(), 8 + Global::V1_SPACE);
+
+        let global = Global::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(global.version, CURRENT_VERSION);
+        assert_eq!(global.global_authority, authority);
+        assert_eq!(global.migration_authority, v1.migration_authority);
+        assert_eq!(global.fee_receiver, v1.fee_receiver);
+        assert_eq!(global.meteora_config, v1.meteora_config);
+        assert_eq!(global.token_total_supply, v1.token_total_supply);
+        assert!(global.whitelist_enabled);
+        assert!(global.pending_params.is_none());
+        for role in Role::ALL {
+            assert!(global.has_role(role, &global.role_holder(role)));
+        }
+        assert!(global.has_role(Role::Pauser, &authority));
+        assert!(global.has_role(Role::Migrator, &v1.migration_authority));
+    }
+
+    #[test]
+    fn test_load_v1_bonding_curve() {
+        let v1 = BondingCurveV1 {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            initial_virtual_token_reserves: 1_073_000_000,
+            virtual_sol_reserves: 30,
+            virtual_token_reserves: 1_000_000_000,
+            real_sol_reserves: 5,
+            real_token_reserves: 700_000_000,
+            token_total_supply: 1_000_000_000,
+            start_time: 1_700_000_000,
+            complete: false,
+            bump: 254,
+        };
+        let data = account_bytes::<BondingCurve>(&v1);
+        assert_eq!(data.len(), 8 + BondingCurve::V1_SPACE);
+
+        let curve = BondingCurve::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(curve.version, CURRENT_VERSION);
+        assert_eq!(curve.mint, v1.mint);
+        assert_eq!(curve.real_sol_reserves, 5);
+        assert_eq!(curve.bump, 254);
+        assert_eq!(curve.status, CurveStatus::Trading);
+        assert!(!curve.paused && !curve.delisted);
+
+        let complete = BondingCurveV1 {
+            complete: true,
+            ..v1
+        };
+        let data = account_bytes::<BondingCurve>(&complete);
+        let curve = BondingCurve::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(curve.status, CurveStatus::Complete);
+    }
+
+    #[test]
+    fn test_current_layout_roundtrip() {
+        let global = Global::default();
+        let mut data = vec![];
+        global.try_serialize(&mut data).unwrap();
+        // no pending params, shorter than the allocated space
+        assert!(data.len() < 8 + Global::INIT_SPACE);
+
+        let loaded = Global::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(loaded.version, CURRENT_VERSION);
+        assert_eq!(loaded.fee_receiver, global.fee_receiver);
+
+        // zeroed tail of a freshly allocated account
+        data.extend_from_slice(&[0; 16]);
+        assert!(Global::try_deserialize(&mut &data[..]).is_ok());
+    }
+
+    #[test]
+    fn test_reject_future_version() {
+        let curve = BondingCurve {
+            version: CURRENT_VERSION + 1,
+            ..Default::default()
+        };
+        let mut data = vec![];
+        curve.try_serialize(&mut data).unwrap();
+        assert_eq!(
+            BondingCurve::try_deserialize(&mut &data[..]).unwrap_err(),
+            ContractError::UnsupportedAccountVersion.into()
+        );
+
+        let mut data = vec![0; 8 + BondingCurve::INIT_SPACE];
+        assert_eq!(
+            BondingCurve::try_deserialize(&mut &data[..]).unwrap_err(),
+            ErrorCode::AccountDiscriminatorMismatch.into()
+        );
+        data.truncate(4);
+        assert_eq!(
+            BondingCurve::try_deserialize(&mut &data[..]).unwrap_err(),
+            ErrorCode::AccountDiscriminatorNotFound.into()
+        );
+    }
+}
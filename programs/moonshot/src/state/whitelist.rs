@@ -1,11 +1,264 @@
-use anchor_lang::prelude::*;
+use crate::errors::ContractError;
+use crate::events::{WhitelistUpdatedEvent, EVENT_SCHEMA_VERSION};
+use crate::state::versioned::{versioned_account, Versioned, CURRENT_VERSION};
+use anchor_lang::{prelude::*, system_program};
 
-#[account]
-#[derive(InitSpace, Debug, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug, Default)]
 pub struct Whitelist {
     pub creator: Pubkey,
+    pub max_launches: Option<u32>, // None for unlimited
+    pub launches_used: u32,
+    pub expires_at: Option<i64>,
+    pub launch_config: Option<u16>, // tier the creator has to launch with
+    pub version: u8,
+}
+
+versioned_account!(Whitelist, [204, 176, 52, 79, 146, 121, 54, 247]);
+
+/// Whitelist as written before quotas existed.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct WhitelistV1 {
+    pub creator: Pubkey,
+}
+
+impl Versioned for Whitelist {
+    const V1_SPACE: usize = 32;
+    type V1 = WhitelistV1;
+
+    /// Entries from before quotas stay unlimited and untiered.
+    fn from_v1(v1: WhitelistV1) -> Self {
+        Whitelist::new(v1.creator)
+    }
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// Limits of a whitelist entry, replaced as a whole by `update_wl`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct WhitelistInput {
+    pub max_launches: Option<u32>,
+    pub expires_at: Option<i64>,
+    pub launch_config: Option<u16>,
 }
 
 impl Whitelist {
     pub const SEED_PREFIX: &'static str = "wl-seed";
-}
\ No newline at end of file
+
+    /// Checks `whitelist` is the entry PDA of `creator`, returns its bump.
+    pub fn check_address(whitelist: &AccountInfo, creator: &Pubkey) -> Result<u8> {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[Self::SEED_PREFIX.as_bytes(), creator.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(whitelist.key(), expected, ContractError::InvalidArgument);
+        Ok(bump)
+    }
+
+    /// Creates the entry of `creator` outside of an `init` constraint, for instructions
+    /// that handle a dynamic set of entries.
+    pub fn create_entry<'info>(
+        whitelist: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        creator: Pubkey,
+    ) -> Result<()> {
+        let bump = Self::check_address(whitelist, &creator)?;
+        require!(whitelist.data_is_empty(), ContractError::AddFailed);
+
+        let space = 8 + Self::INIT_SPACE;
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: whitelist.clone(),
+                },
+                &[&[Self::SEED_PREFIX.as_bytes(), creator.as_ref(), &[bump]]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+        let mut data = whitelist.try_borrow_mut_data()?;
+        Self::new(creator).try_serialize(&mut &mut data[..])
+    }
+
+    /// Same as `close = receiver` on an entry.
+    pub fn close_entry<'info>(
+        whitelist: &AccountInfo<'info>,
+        receiver: &AccountInfo<'info>,
+        creator: &Pubkey,
+    ) -> Result<()> {
+        Self::check_address(whitelist, creator)?;
+        require!(
+            *whitelist.owner == crate::ID && !whitelist.data_is_empty(),
+            ContractError::RemoveFailed
+        );
+
+        let lamports = whitelist.get_lamports();
+        whitelist.sub_lamports(lamports)?;
+        receiver.add_lamports(lamports)?;
+        whitelist.assign(&system_program::ID);
+        whitelist.realloc(0, false)?;
+        Ok(())
+    }
+
+    pub fn new(creator: Pubkey) -> Self {
+        Whitelist {
+            creator,
+            version: CURRENT_VERSION,
+            ..Default::default()
+        }
+    }
+
+    pub fn update(&mut self, input: WhitelistInput) {
+        self.max_launches = input.max_launches;
+        self.expires_at = input.expires_at;
+        self.launch_config = input.launch_config;
+        self.version = CURRENT_VERSION;
+    }
+
+    /// Checks the entry allows a launch with `launch_config` and counts it.
+    pub fn use_launch(&mut self, launch_config: Option<u16>, now: i64) -> Result<()> {
+        if let Some(expires_at) = self.expires_at {
+            require_gt!(expires_at, now, ContractError::WhitelistExpired);
+        }
+        if let Some(max_launches) = self.max_launches {
+            require_gt!(
+                max_launches,
+                self.launches_used,
+                ContractError::LaunchQuotaExceeded
+            );
+        }
+        if self.launch_config.is_some() {
+            require!(
+                self.launch_config == launch_config,
+                ContractError::WhitelistTierMismatch
+            );
+        }
+        self.launches_used = self
+            .launches_used
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Reports the entry as it stands after an `update`.
+    pub fn updated_event(
+        &self,
+        whitelist: Pubkey,
+        authority: Pubkey,
+        timestamp: i64,
+    ) -> WhitelistUpdatedEvent {
+        WhitelistUpdatedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            creator: self.creator,
+            whitelist,
+            max_launches: self.max_launches,
+            launches_used: self.launches_used,
+            expires_at: self.expires_at,
+            launch_config: self.launch_config,
+            authority,
+            timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_entry() {
+        let mut entry = Whitelist::new(Pubkey::new_unique());
+        for _ in 0..100 {
+            entry.use_launch(None, i64::MAX).unwrap();
+        }
+        entry.use_launch(Some(3), 0).unwrap();
+        assert_eq!(entry.launches_used, 101);
+    }
+
+    #[test]
+    fn test_entry_limits() {
+        let mut entry = Whitelist::new(Pubkey::new_unique());
+        entry.update(WhitelistInput {
+            max_launches: Some(2),
+            expires_at: Some(1_000),
+            launch_config: Some(1),
+        });
+
+        assert_eq!(
+            entry.use_launch(None, 0).unwrap_err(),
+            ContractError::WhitelistTierMismatch.into()
+        );
+        assert_eq!(
+            entry.use_launch(Some(2), 0).unwrap_err(),
+            ContractError::WhitelistTierMismatch.into()
+        );
+        assert_eq!(
+            entry.use_launch(Some(1), 1_000).unwrap_err(),
+            ContractError::WhitelistExpired.into()
+        );
+        assert_eq!(entry.launches_used, 0);
+
+        entry.use_launch(Some(1), 999).unwrap();
+        entry.use_launch(Some(1), 999).unwrap();
+        assert_eq!(
+            entry.use_launch(Some(1), 999).unwrap_err(),
+            ContractError::LaunchQuotaExceeded.into()
+        );
+
+        // raising the quota keeps the count
+        entry.update(WhitelistInput {
+            max_launches: Some(3),
+            ..Default::default()
+        });
+        entry.use_launch(None, i64::MAX).unwrap();
+        assert_eq!(entry.launches_used, 3);
+    }
+
+    #[test]
+    fn test_check_address() {
+        let creator = Pubkey::new_unique();
+        let (pda, bump) = Pubkey::find_program_address(
+            &[Whitelist::SEED_PREFIX.as_bytes(), creator.as_ref()],
+            &crate::ID,
+        );
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let info = AccountInfo::new(
+            &pda,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(Whitelist::check_address(&info, &creator).unwrap(), bump);
+
+        // a batch with the accounts out of order fails on the first mismatch
+        assert_eq!(
+            Whitelist::check_address(&info, &Pubkey::new_unique()).unwrap_err(),
+            ContractError::InvalidArgument.into()
+        );
+    }
+
+    #[test]
+    fn test_load_v1_whitelist() {
+        let creator = Pubkey::new_unique();
+        let mut data = Whitelist::DISCRIMINATOR.to_vec();
+        WhitelistV1 { creator }.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + 32);
+
+        let entry = Whitelist::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(entry.creator, creator);
+        assert_eq!(entry.max_launches, None);
+        assert_eq!(entry.expires_at, None);
+        assert_eq!(entry.version, CURRENT_VERSION);
+    }
+}
@@ -1,6 +1,13 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 
 pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
 
+pub fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
 pub fn bps_mul(bps: u64, value: u64, divisor: u64) -> Option<u64> {
     bps_mul_raw(bps, value, divisor).unwrap().try_into().ok()
 }
@@ -9,4 +16,4 @@ pub fn bps_mul_raw(bps: u64, value: u64, divisor: u64) -> Option<u128> {
     (value as u128)
         .checked_mul(bps as u128)?
         .checked_div(divisor as u128)
-}
\ No newline at end of file
+}